@@ -0,0 +1,283 @@
+//! Derive macros for the [no_debug](https://docs.rs/no_debug) crate.
+//!
+//! These are re-exported by `no_debug` when its `derive` feature is enabled and should be used
+//! from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Spacing, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Fields, Ident, Meta, Type,
+    WherePredicate,
+};
+
+/// Derives [Debug] for a struct or enum, printing fields marked with `#[no_debug]` through a
+/// `no_debug::Msg` implementation instead of their own [Debug] impl.
+///
+/// - `#[no_debug]` uses `no_debug::WithTypeInfo`.
+/// - `#[no_debug(msg = M)]` uses the marker type `M`, which must implement `Msg<FieldType>`.
+///
+/// Fields without the attribute are printed as they would be by `#[derive(Debug)]`. The attribute
+/// is rejected on structs, enums and variants, rather than being ignored.
+///
+/// Like `#[derive(Debug)]`, generic types only require `T: Debug` for the type parameters used by
+/// fields that are printed with [Debug], so recursive types like trees are supported.
+#[proc_macro_derive(RedactedDebug, attributes(no_debug))]
+pub fn derive_redacted_debug(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    reject_no_debug(&input.attrs)?;
+    let mut bounds = Bounds {
+        name,
+        params: input
+            .generics
+            .type_params()
+            .map(|param| &param.ident)
+            .collect(),
+        predicates: vec![],
+    };
+    let arms = match &input.data {
+        Data::Struct(data) => {
+            let arm = expand_variant(&parse_quote!(Self), name, &data.fields, &mut bounds)?;
+            vec![arm]
+        }
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                reject_no_debug(&variant.attrs)?;
+                let ident = &variant.ident;
+                expand_variant(
+                    &parse_quote!(Self::#ident),
+                    ident,
+                    &variant.fields,
+                    &mut bounds,
+                )
+            })
+            .collect::<syn::Result<_>>()?,
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "RedactedDebug cannot be derived for unions",
+            ))
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    if !bounds.predicates.is_empty() {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(bounds.predicates);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    // Empty enums can't be matched through a reference.
+    let scrutinee = if arms.is_empty() {
+        quote! { *self }
+    } else {
+        quote! { self }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Debug for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                match #scrutinee {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+fn expand_variant(
+    path: &syn::Path,
+    name: &Ident,
+    fields: &Fields,
+    bounds: &mut Bounds,
+) -> syn::Result<TokenStream2> {
+    // Raw identifiers are printed without their prefix, like `#[derive(Debug)]` does.
+    let name = name.unraw().to_string();
+    let mut bindings = vec![];
+    let mut values = vec![];
+    for (index, field) in fields.iter().enumerate() {
+        let binding = format_ident!("__self_{}", index);
+        let ty = &field.ty;
+        let value = match field_msg(&field.attrs)? {
+            Some(msg) => {
+                bounds.add_msg(ty, &msg);
+                quote! { &::no_debug::NoDebugRef::<#ty, #msg>::wrap(#binding) }
+            }
+            None => {
+                bounds.add_debug(ty);
+                quote! { #binding }
+            }
+        };
+        bindings.push(binding);
+        values.push(value);
+    }
+
+    Ok(match fields {
+        Fields::Named(_) => {
+            let idents: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+            let labels = idents
+                .iter()
+                .map(|ident| ident.as_ref().expect("named field").unraw().to_string());
+            quote! {
+                #path { #(#idents: #bindings),* } => f
+                    .debug_struct(#name)
+                    #(.field(#labels, #values))*
+                    .finish(),
+            }
+        }
+        Fields::Unnamed(_) => quote! {
+            #path(#(#bindings),*) => f
+                .debug_tuple(#name)
+                #(.field(#values))*
+                .finish(),
+        },
+        Fields::Unit => quote! {
+            #path => f.write_str(#name),
+        },
+    })
+}
+
+/// The where clause of a derived impl.
+///
+/// Bounding field types (e.g. `Vec<Tree<T>>: Debug`) would make recursive types unusable, as
+/// proving the bound needs the impl being defined. Like `#[derive(Debug)]`, only type parameters
+/// are bounded instead, and fields that name the type itself are skipped.
+struct Bounds<'a> {
+    name: &'a Ident,
+    params: Vec<&'a Ident>,
+    predicates: Vec<WherePredicate>,
+}
+
+impl Bounds<'_> {
+    /// Bounds the type parameters used by a field that is printed with [Debug].
+    fn add_debug(&mut self, ty: &Type) {
+        let idents = idents(ty.to_token_stream());
+        if self.names_self(&idents) {
+            return;
+        }
+        // Associated types like `T::Item` aren't covered by `T: Debug`, so bound the field type.
+        if idents
+            .iter()
+            .any(|(ident, is_path)| *is_path && self.params.contains(&ident))
+        {
+            self.push(parse_quote!(#ty: ::core::fmt::Debug));
+            return;
+        }
+        for param in self.params.clone() {
+            if idents.iter().any(|(ident, _)| ident == param) {
+                self.push(parse_quote!(#param: ::core::fmt::Debug));
+            }
+        }
+    }
+
+    /// Requires `msg` to print a field's type, if it depends on the type parameters.
+    fn add_msg(&mut self, ty: &Type, msg: &Type) {
+        let mut idents = idents(ty.to_token_stream());
+        if self.names_self(&idents) {
+            return;
+        }
+        idents.extend(self::idents(msg.to_token_stream()));
+        if idents.iter().any(|(ident, _)| self.params.contains(&ident)) {
+            self.push(parse_quote!(#msg: ::no_debug::Msg<#ty>));
+        }
+    }
+
+    fn names_self(&self, idents: &[(Ident, bool)]) -> bool {
+        idents
+            .iter()
+            .any(|(ident, _)| ident == self.name || ident == "Self")
+    }
+
+    fn push(&mut self, predicate: WherePredicate) {
+        let key = predicate.to_token_stream().to_string();
+        if !self
+            .predicates
+            .iter()
+            .any(|existing| existing.to_token_stream().to_string() == key)
+        {
+            self.predicates.push(predicate);
+        }
+    }
+}
+
+/// Lists the identifiers in `tokens`, with whether each starts a path (i.e. is followed by `::`).
+fn idents(tokens: TokenStream2) -> Vec<(Ident, bool)> {
+    let mut idents = vec![];
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) => {
+                let is_path = matches!(
+                    tokens.peek(),
+                    Some(TokenTree::Punct(punct))
+                        if punct.as_char() == ':' && punct.spacing() == Spacing::Joint
+                );
+                idents.push((ident, is_path));
+            }
+            TokenTree::Group(group) => idents.extend(self::idents(group.stream())),
+            _ => {}
+        }
+    }
+    idents
+}
+
+/// Rejects `#[no_debug]` on items and variants, where it would otherwise be silently ignored and
+/// leave their fields printed in full.
+fn reject_no_debug(attrs: &[Attribute]) -> syn::Result<()> {
+    match attrs.iter().find(|attr| attr.path().is_ident("no_debug")) {
+        Some(attr) => Err(syn::Error::new_spanned(
+            attr,
+            "`#[no_debug]` is only supported on fields",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Finds the `Msg` marker requested by a field's `#[no_debug]` attribute, if any.
+fn field_msg(attrs: &[Attribute]) -> syn::Result<Option<Type>> {
+    let mut msg = None;
+    for attr in attrs {
+        if !attr.path().is_ident("no_debug") {
+            continue;
+        }
+        if msg.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "duplicate `#[no_debug]` attribute",
+            ));
+        }
+        msg = Some(match &attr.meta {
+            Meta::Path(_) => parse_quote!(::no_debug::WithTypeInfo),
+            Meta::List(_) => {
+                let mut ty = None;
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("msg") {
+                        ty = Some(meta.value()?.parse::<Type>()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `msg = ...`"))
+                    }
+                })?;
+                ty.ok_or_else(|| syn::Error::new_spanned(attr, "expected `msg = ...`"))?
+            }
+            Meta::NameValue(_) => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "expected `#[no_debug]` or `#[no_debug(msg = ...)]`",
+                ))
+            }
+        });
+    }
+    Ok(msg)
+}
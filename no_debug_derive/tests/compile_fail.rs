@@ -0,0 +1,4 @@
+#[test]
+fn rejects_misplaced_attributes() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,190 @@
+use no_debug::{Ellipses, Msg};
+use no_debug_derive::RedactedDebug;
+
+struct Hidden;
+
+impl<T> Msg<T> for Hidden {
+    fn fmt(_value: &T, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "<hidden>")
+    }
+}
+
+#[derive(RedactedDebug)]
+struct UserInfo {
+    username: String,
+    #[no_debug]
+    password: String,
+    #[no_debug(msg = Ellipses)]
+    posts: Vec<String>,
+}
+
+#[derive(RedactedDebug)]
+struct Token(#[no_debug(msg = Hidden)] String, u32);
+
+#[derive(RedactedDebug)]
+struct Unit;
+
+#[derive(RedactedDebug)]
+enum Credentials {
+    Anonymous,
+    Password {
+        username: String,
+        #[no_debug]
+        password: String,
+    },
+    Key(#[no_debug(msg = Ellipses)] Vec<u8>),
+}
+
+#[derive(RedactedDebug)]
+struct Wrapper<T, U> {
+    shown: T,
+    #[no_debug(msg = Ellipses)]
+    hidden: U,
+}
+
+struct NotDebug;
+
+#[derive(RedactedDebug)]
+struct r#Raw {
+    r#type: i32,
+    #[no_debug(msg = Ellipses)]
+    r#ref: i32,
+}
+
+#[derive(RedactedDebug)]
+enum Keyword {
+    r#Match(#[no_debug(msg = Ellipses)] i32),
+}
+
+#[derive(RedactedDebug)]
+struct Tree<T> {
+    value: T,
+    #[no_debug(msg = Ellipses)]
+    secret: String,
+    children: Vec<Tree<T>>,
+}
+
+#[derive(RedactedDebug)]
+struct Items<I: Iterator> {
+    first: Option<I::Item>,
+    #[no_debug(msg = Ellipses)]
+    rest: I,
+}
+
+#[test]
+fn redacts_marked_fields() {
+    let user = UserInfo {
+        username: "Cypher1".to_string(),
+        password: "hunter2".to_string(),
+        posts: vec!["long post 1...".to_string()],
+    };
+    assert_eq!(
+        format!("{:?}", user),
+        r#"UserInfo { username: "Cypher1", password: <no debug: alloc::string::String>, posts: ... }"#
+    );
+}
+
+#[test]
+fn supports_pretty_printing() {
+    let user = UserInfo {
+        username: "Cypher1".to_string(),
+        password: "hunter2".to_string(),
+        posts: vec![],
+    };
+    assert_eq!(
+        format!("{:#?}", user),
+        r#"UserInfo {
+    username: "Cypher1",
+    password: <no debug: alloc::string::String>,
+    posts: ...,
+}"#
+    );
+}
+
+#[test]
+fn field_types_stay_plain() {
+    let user = UserInfo {
+        username: "Cypher1".to_string(),
+        password: "hunter2".to_string(),
+        posts: vec![],
+    };
+    assert_eq!(user.password.len(), 7);
+    assert!(user.posts.is_empty());
+}
+
+#[test]
+fn supports_custom_msg_in_tuple_structs() {
+    let token = Token("secret".to_string(), 3);
+    assert_eq!(format!("{:?}", token), "Token(<hidden>, 3)");
+}
+
+#[test]
+fn supports_unit_structs() {
+    assert_eq!(format!("{:?}", Unit), "Unit");
+}
+
+#[test]
+fn supports_enums() {
+    assert_eq!(format!("{:?}", Credentials::Anonymous), "Anonymous");
+    let password = Credentials::Password {
+        username: "Cypher1".to_string(),
+        password: "hunter2".to_string(),
+    };
+    assert_eq!(
+        format!("{:?}", password),
+        r#"Password { username: "Cypher1", password: <no debug: alloc::string::String> }"#
+    );
+    assert_eq!(format!("{:?}", Credentials::Key(vec![1, 2, 3])), "Key(...)");
+}
+
+#[test]
+fn does_not_require_debug_for_redacted_generic_fields() {
+    let wrapper = Wrapper {
+        shown: 3,
+        hidden: NotDebug,
+    };
+    assert_eq!(
+        format!("{:?}", wrapper),
+        "Wrapper { shown: 3, hidden: ... }"
+    );
+}
+
+#[test]
+fn supports_recursive_generic_types() {
+    let tree = Tree {
+        value: 1,
+        secret: "hunter2".to_string(),
+        children: vec![Tree {
+            value: 2,
+            secret: "hunter3".to_string(),
+            children: vec![],
+        }],
+    };
+    assert_eq!(
+        format!("{:?}", tree),
+        "Tree { value: 1, secret: ..., children: [Tree { value: 2, secret: ..., children: [] }] }"
+    );
+}
+
+#[test]
+fn bounds_associated_types() {
+    let mut rest = vec![NotDebug, NotDebug].into_iter().map(|_| 3);
+    let items = Items {
+        first: rest.next(),
+        rest,
+    };
+    assert_eq!(
+        format!("{:?}", items),
+        "Items { first: Some(3), rest: ... }"
+    );
+}
+
+#[test]
+fn prints_raw_identifiers_without_prefix() {
+    let raw = Raw {
+        r#type: 1,
+        r#ref: 2,
+    };
+    assert_eq!(format!("{:?}", raw), "Raw { type: 1, ref: ... }");
+    assert_eq!(format!("{:?}", Keyword::Match(3)), "Match(...)");
+}
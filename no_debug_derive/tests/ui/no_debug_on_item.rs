@@ -0,0 +1,7 @@
+use no_debug_derive::RedactedDebug;
+
+#[derive(RedactedDebug)]
+#[no_debug]
+struct Key(String);
+
+fn main() {}
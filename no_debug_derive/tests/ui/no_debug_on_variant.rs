@@ -0,0 +1,9 @@
+use no_debug_derive::RedactedDebug;
+
+#[derive(RedactedDebug)]
+enum Credentials {
+    #[no_debug]
+    Token(String),
+}
+
+fn main() {}
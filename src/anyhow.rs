@@ -0,0 +1,19 @@
+//! Adds context to [anyhow] errors without leaking the underlying error.
+//! ```rust
+//! use no_debug::anyhow::RedactedContext;
+//! use no_debug::NoDebugExt;
+//!
+//! fn login(password: &str) -> anyhow::Result<()> {
+//!     let result: Result<(), std::io::Error> =
+//!         Err(std::io::Error::other(format!("bad password {}", password)));
+//!     result.with_redacted_context(|| format!("login failed for {:?}", password.no_debug()))
+//! }
+//!
+//! let error = login("hunter2").unwrap_err();
+//! assert_eq!(
+//!     format!("{:#}", error),
+//!     "login failed for <no debug: &str>: <redacted error>"
+//! );
+//! ```
+
+redacted_context!(anyhow, ::anyhow::Error, context, "anyhow::Context");
@@ -0,0 +1,137 @@
+use crate::RedactedError;
+use core::error::Error;
+use core::fmt::{Debug, Display};
+
+/// Marks errors that have already been redacted by a `RedactedContext` trait, so that adding more
+/// context doesn't redact the context added before it.
+pub(crate) struct Redacted(pub(crate) RedactedError);
+
+impl Display for Redacted {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Debug for Redacted {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Error for Redacted {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Defines a `RedactedContext` trait for an error reporting crate, e.g. `anyhow`.
+///
+/// Takes the crate name, its error type, the method that adds context to that error type and the
+/// trait that method belongs to.
+macro_rules! redacted_context {
+    ($krate: ident, $error: ty, $context: ident, $like: literal) => {
+        use core::fmt::Display;
+
+        #[doc = concat!("Like [", $like, "], but replaces the underlying error with a")]
+        /// [RedactedError](crate::RedactedError), so reports only contain the context.
+        ///
+        /// Context added by earlier calls is kept, only the original error is redacted.
+        ///
+        /// Use [NoDebug](crate::NoDebug) or [NoDebugExt](crate::NoDebugExt) to include sensitive
+        /// values in the context.
+        pub trait RedactedContext<T> {
+            /// Wraps the error value with redacted context.
+            fn redacted_context<C>(self, context: C) -> ::$krate::Result<T>
+            where
+                C: Display + Send + Sync + 'static;
+
+            /// Wraps the error value with redacted context that is only evaluated when an error
+            /// occurs.
+            fn with_redacted_context<C, F>(self, context: F) -> ::$krate::Result<T>
+            where
+                C: Display + Send + Sync + 'static,
+                F: FnOnce() -> C;
+        }
+
+        impl<T, E: Into<$error>> RedactedContext<T> for Result<T, E> {
+            fn redacted_context<C>(self, context: C) -> ::$krate::Result<T>
+            where
+                C: Display + Send + Sync + 'static,
+            {
+                self.with_redacted_context(|| context)
+            }
+
+            fn with_redacted_context<C, F>(self, context: F) -> ::$krate::Result<T>
+            where
+                C: Display + Send + Sync + 'static,
+                F: FnOnce() -> C,
+            {
+                self.map_err(|error| {
+                    let error: $error = error.into();
+                    if error.downcast_ref::<crate::context::Redacted>().is_some() {
+                        return error.$context(context());
+                    }
+                    let error = crate::context::Redacted(crate::RedactedError::new(&*error));
+                    <$error>::new(error).$context(context())
+                })
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+            use crate::{Ellipses, NoDebug};
+            use std::prelude::rust_2021::*;
+
+            struct Credentials {
+                password: NoDebug<String, Ellipses>,
+            }
+
+            fn check(credentials: &Credentials) -> Result<(), std::io::Error> {
+                Err(std::io::Error::other(format!(
+                    "rejected password {}",
+                    *credentials.password
+                )))
+            }
+
+            fn credentials() -> Credentials {
+                Credentials {
+                    password: "hunter2".to_string().into(),
+                }
+            }
+
+            #[test]
+            fn redacts_underlying_errors() {
+                let credentials = credentials();
+                let error = check(&credentials)
+                    .redacted_context(format!("login failed for {:?}", credentials.password))
+                    .unwrap_err();
+                assert_eq!(format!("{}", error), "login failed for ...");
+                assert_eq!(
+                    format!("{:#}", error),
+                    "login failed for ...: <redacted error>"
+                );
+                let report = format!("{:?}", error);
+                assert!(
+                    report.starts_with("login failed for ...\n\nCaused by:\n    <redacted error>")
+                );
+                assert!(!report.contains("hunter2"));
+            }
+
+            #[test]
+            fn keeps_earlier_redacted_context() {
+                let credentials = credentials();
+                let error = check(&credentials)
+                    .map_err(<$error>::from)
+                    .with_redacted_context(|| "login failed")
+                    .redacted_context("request failed")
+                    .unwrap_err();
+                assert_eq!(
+                    format!("{:#}", error),
+                    "request failed: login failed: <redacted error>"
+                );
+                assert!(!format!("{:?}", error).contains("hunter2"));
+            }
+        }
+    };
+}
@@ -0,0 +1,236 @@
+use crate::{Msg, MsgDisplay, NoDebug};
+use core::error::Error;
+use core::fmt::{Debug, Display};
+
+/// The most causes of an error that are kept when redacting it, any further causes are dropped.
+const MAX_CAUSES: usize = 32;
+
+/// Stands in for the `n`th cause in a redacted error's [source](Error::source) chain.
+#[derive(Clone, Copy)]
+struct RedactedCause(usize);
+
+/// Redacted chains of up to [MAX_CAUSES] causes are suffixes of this chain, so they can be
+/// returned from [Error::source] without allocating or borrowing the original causes.
+static CAUSES: [RedactedCause; MAX_CAUSES] = {
+    let mut causes = [RedactedCause(0); MAX_CAUSES];
+    let mut index = 0;
+    while index < MAX_CAUSES {
+        causes[index] = RedactedCause(index);
+        index += 1;
+    }
+    causes
+};
+
+/// Counts the causes in `source`'s chain (up to [MAX_CAUSES]).
+fn count_causes(source: Option<&(dyn Error + 'static)>) -> usize {
+    core::iter::successors(source, |&cause| cause.source())
+        .take(MAX_CAUSES)
+        .count()
+}
+
+/// Gets a chain of `count` redacted causes.
+fn redacted_causes(count: usize) -> Option<&'static (dyn Error + 'static)> {
+    if count == 0 {
+        return None;
+    }
+    Some(&CAUSES[MAX_CAUSES - count])
+}
+
+impl Display for RedactedCause {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        write!(f, "<redacted error>")
+    }
+}
+
+impl Debug for RedactedCause {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        Display::fmt(self, f)
+    }
+}
+
+impl Error for RedactedCause {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        CAUSES
+            .get(self.0 + 1)
+            .map(|cause| cause as &(dyn Error + 'static))
+    }
+}
+
+/// Wrapped errors are printed using `M`, and their causes are printed as `<redacted error>`.
+/// ```rust
+/// use no_debug::{Ellipses, NoDebug};
+///
+/// fn connect() -> Result<(), NoDebug<std::io::Error, Ellipses>> {
+///     Err(std::io::Error::other("could not connect with password hunter2").into())
+/// }
+///
+/// let error: Box<dyn std::error::Error> = connect().unwrap_err().into();
+/// assert_eq!(error.to_string(), "...");
+/// ```
+impl<E: Error + ?Sized, M: MsgDisplay<E>> Error for NoDebug<E, M> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        redacted_causes(count_causes(self.as_inner().source()))
+    }
+}
+
+/// An error that keeps only the shape of another error's [source](Error::source) chain, printing
+/// it and each of its causes as `<redacted error>`.
+///
+/// This is useful for returning errors across trust boundaries, e.g. from a service to its
+/// clients.
+/// ```rust
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use no_debug::RedactedError;
+///
+/// let error: Box<dyn std::error::Error + Send + Sync> = "user hunter2 not found".into();
+/// let error = RedactedError::from(error);
+/// assert_eq!(error.to_string(), "<redacted error>");
+/// assert_eq!(format!("{:?}", error), "<redacted error>");
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct RedactedError {
+    causes: usize,
+}
+
+impl RedactedError {
+    /// Redacts an error and its causes.
+    pub fn new(error: &(dyn Error + 'static)) -> Self {
+        Self {
+            causes: count_causes(error.source()),
+        }
+    }
+}
+
+impl Display for RedactedError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        write!(f, "<redacted error>")
+    }
+}
+
+impl Debug for RedactedError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        Display::fmt(self, f)
+    }
+}
+
+impl Error for RedactedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        redacted_causes(self.causes)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<alloc::boxed::Box<dyn Error>> for RedactedError {
+    fn from(error: alloc::boxed::Box<dyn Error>) -> Self {
+        Self::new(&*error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<alloc::boxed::Box<dyn Error + Send + Sync>> for RedactedError {
+    fn from(error: alloc::boxed::Box<dyn Error + Send + Sync>) -> Self {
+        Self::new(&*error)
+    }
+}
+
+impl<E: Error + 'static, M: Msg<E>> From<NoDebug<E, M>> for RedactedError {
+    fn from(error: NoDebug<E, M>) -> Self {
+        Self::new(error.as_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ellipses;
+    use std::prelude::rust_2021::*;
+
+    #[derive(Debug)]
+    struct QueryError {
+        query: &'static str,
+        source: Option<Box<QueryError>>,
+    }
+
+    impl Display for QueryError {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+            write!(f, "query failed: {}", self.query)
+        }
+    }
+
+    impl Error for QueryError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.source
+                .as_deref()
+                .map(|source| source as &(dyn Error + 'static))
+        }
+    }
+
+    fn chain(length: usize) -> QueryError {
+        (1..length).fold(
+            QueryError {
+                query: "SELECT password FROM users",
+                source: None,
+            },
+            |source, _| QueryError {
+                query: "SELECT token FROM sessions",
+                source: Some(Box::new(source)),
+            },
+        )
+    }
+
+    fn messages(error: &(dyn Error + 'static)) -> Vec<String> {
+        core::iter::successors(Some(error), |&cause| cause.source())
+            .map(|cause| format!("{} / {:?}", cause, cause))
+            .collect()
+    }
+
+    #[test]
+    fn redacts_error_and_causes() {
+        let error: NoDebug<QueryError, Ellipses> = chain(3).into();
+        assert_eq!(
+            messages(&error),
+            [
+                "... / ...",
+                "<redacted error> / <redacted error>",
+                "<redacted error> / <redacted error>",
+            ]
+        );
+    }
+
+    #[test]
+    fn redacts_errors_without_causes() {
+        let error: NoDebug<QueryError> = chain(1).into();
+        assert!(error.source().is_none());
+        assert_eq!(
+            error.to_string(),
+            "<no debug: no_debug::error::tests::QueryError>"
+        );
+    }
+
+    #[test]
+    fn redacted_error_keeps_chain_length() {
+        let original = chain(4);
+        let error = RedactedError::new(&original);
+        assert_eq!(
+            messages(&error),
+            vec!["<redacted error> / <redacted error>"; 4]
+        );
+    }
+
+    #[test]
+    fn redacted_error_truncates_long_chains() {
+        let error = RedactedError::new(&chain(100));
+        assert_eq!(messages(&error).len(), MAX_CAUSES + 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn redacts_boxed_errors() {
+        let original: Box<dyn Error + Send + Sync> = Box::new(chain(2));
+        let error = RedactedError::from(original);
+        assert_eq!(messages(&error).len(), 2);
+        assert!(!messages(&error).concat().contains("SELECT"));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    Ellipses, Fingerprint, Masked, MaxDepth, Msg, NoDebug, NoDebugRef, ShowFirst, ShowLast,
+    Summarize, Summary, Truncate,
+};
+use core::fmt::Debug;
+use core::hash::Hash;
+
+/// Adds methods to every type for wrapping values in [NoDebug], or printing them using a [Msg]
+/// type without changing their type.
+///
+/// The `no_debug` methods borrow the value, the others take ownership of it.
+/// ```rust
+/// use no_debug::{Ellipses, NoDebug, NoDebugExt};
+///
+/// let password = "hunter2".to_string();
+/// assert_eq!(format!("{:?}", password.no_debug()), "<no debug: alloc::string::String>");
+/// assert_eq!(format!("{:?}", password.no_debug_with::<Ellipses>()), "...");
+///
+/// let password: NoDebug<String, Ellipses> = password.redacted();
+/// assert_eq!(format!("{:?}", password), "...");
+/// ```
+pub trait NoDebugExt: Sized {
+    /// Borrows the value, printing it using [WithTypeInfo](crate::WithTypeInfo).
+    fn no_debug(&self) -> NoDebugRef<'_, Self> {
+        NoDebugRef::new(self)
+    }
+
+    /// Borrows the value, printing it using `M`.
+    fn no_debug_with<M: Msg<Self>>(&self) -> NoDebugRef<'_, Self, M> {
+        NoDebugRef::wrap(self)
+    }
+
+    /// Wraps the value, printing it using [WithTypeInfo](crate::WithTypeInfo).
+    fn into_no_debug(self) -> NoDebug<Self> {
+        NoDebug::new(self)
+    }
+
+    /// Wraps the value, printing it using `M`.
+    fn into_no_debug_with<M: Msg<Self>>(self) -> NoDebug<Self, M> {
+        NoDebug::wrap(self)
+    }
+
+    /// Wraps the value, printing it using [Ellipses].
+    fn redacted(self) -> NoDebug<Self, Ellipses> {
+        NoDebug::wrap(self)
+    }
+
+    /// Wraps the value, printing it using [Summary].
+    fn summarized(self) -> NoDebug<Self, Summary>
+    where
+        Self: Summarize,
+    {
+        NoDebug::wrap(self)
+    }
+
+    /// Wraps the value, printing it using [Fingerprint].
+    fn fingerprinted(self) -> NoDebug<Self, Fingerprint>
+    where
+        Self: Hash,
+    {
+        NoDebug::wrap(self)
+    }
+
+    /// Wraps the value, printing it using [Truncate].
+    fn truncated<const N: usize>(self) -> NoDebug<Self, Truncate<N>>
+    where
+        Self: Debug,
+    {
+        NoDebug::wrap(self)
+    }
+
+    /// Wraps the value, printing it using [MaxDepth].
+    fn max_depth<const N: usize>(self) -> NoDebug<Self, MaxDepth<N>>
+    where
+        Self: Debug,
+    {
+        NoDebug::wrap(self)
+    }
+
+    /// Wraps the value, printing it using [ShowLast].
+    fn show_last<const N: usize>(self) -> NoDebug<Self, ShowLast<N>>
+    where
+        Self: AsRef<str>,
+    {
+        NoDebug::wrap(self)
+    }
+
+    /// Wraps the value, printing it using [ShowFirst].
+    fn show_first<const N: usize>(self) -> NoDebug<Self, ShowFirst<N>>
+    where
+        Self: AsRef<str>,
+    {
+        NoDebug::wrap(self)
+    }
+
+    /// Wraps the value, printing it using [Masked].
+    fn masked(self) -> NoDebug<Self, Masked>
+    where
+        Self: AsRef<str>,
+    {
+        NoDebug::wrap(self)
+    }
+}
+
+impl<T> NoDebugExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WithTypeInfo;
+    use std::prelude::rust_2021::*;
+
+    #[test]
+    fn borrows_with_type_info() {
+        let value = vec![1, 2, 3];
+        assert_eq!(
+            format!("{:?}", value.no_debug()),
+            "<no debug: alloc::vec::Vec<i32>>"
+        );
+    }
+
+    #[test]
+    fn borrows_with_msg() {
+        assert_eq!(format!("{:?}", 3.no_debug_with::<Ellipses>()), "...");
+    }
+
+    #[test]
+    fn borrows_nodebug_values() {
+        let value: NoDebug<i32, Ellipses> = 3.into();
+        assert_eq!(
+            format!("{:?}", value.no_debug()),
+            "<no debug: no_debug::NoDebug<i32, no_debug::Ellipses>>"
+        );
+    }
+
+    #[test]
+    fn wraps_values() {
+        assert_eq!(format!("{:?}", 3.into_no_debug()), "<no debug: i32>");
+        assert_eq!(
+            format!("{:?}", 3.into_no_debug_with::<WithTypeInfo>()),
+            "<no debug: i32>"
+        );
+        assert_eq!(format!("{:?}", 3.into_no_debug_with::<Ellipses>()), "...");
+        assert_eq!(format!("{:?}", 3.redacted()), "...");
+        assert_eq!(*3.redacted(), 3);
+    }
+
+    #[test]
+    fn wraps_values_with_builtin_msgs() {
+        assert_eq!(
+            format!("{:?}", [1, 2, 3].summarized()),
+            "<[i32; 3]: 3 items>"
+        );
+        assert_eq!(
+            format!("{:?}", 3.fingerprinted()),
+            format!("{:?}", NoDebug::<_, Fingerprint>::wrap(3))
+        );
+        assert_eq!(
+            format!("{:?}", "hunter2".truncated::<4>()),
+            "\"hun… (+5 more)"
+        );
+        assert_eq!(
+            format!("{:?}", Some(Some(3)).max_depth::<1>()),
+            "Some(Some(..))"
+        );
+        assert_eq!(format!("{:?}", "hunter2".show_last::<2>()), "*****r2");
+        assert_eq!(format!("{:?}", "hunter2".show_first::<2>()), "hu*****");
+        assert_eq!(format!("{:?}", "hunter2".masked()), "*******");
+    }
+}
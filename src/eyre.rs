@@ -0,0 +1,19 @@
+//! Adds context to [eyre] reports without leaking the underlying error.
+//! ```rust
+//! use no_debug::eyre::RedactedContext;
+//! use no_debug::NoDebugExt;
+//!
+//! fn login(password: &str) -> eyre::Result<()> {
+//!     let result: Result<(), std::io::Error> =
+//!         Err(std::io::Error::other(format!("bad password {}", password)));
+//!     result.with_redacted_context(|| format!("login failed for {:?}", password.no_debug()))
+//! }
+//!
+//! let error = login("hunter2").unwrap_err();
+//! assert_eq!(
+//!     format!("{:#}", error),
+//!     "login failed for <no debug: &str>: <redacted error>"
+//! );
+//! ```
+
+redacted_context!(eyre, ::eyre::Report, wrap_err, "eyre::WrapErr");
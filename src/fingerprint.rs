@@ -0,0 +1,169 @@
+use crate::{Msg, MsgDisplay};
+use core::hash::{Hash, Hasher};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+// Stored as two halves, as many embedded targets don't support 64 bit atomics.
+static SALT: [AtomicU32; 2] = [AtomicU32::new(0), AtomicU32::new(0)];
+
+/// Sets the salt used to key the fingerprints printed by [Fingerprint] in this process.
+///
+/// Without a secret salt, values from a small set (e.g. PINs) can be recovered by hashing every
+/// candidate. Set this once at startup, as fingerprints are only comparable if they were computed
+/// with the same salt.
+pub fn set_fingerprint_salt(salt: u64) {
+    SALT[0].store((salt >> 32) as u32, Ordering::Relaxed);
+    SALT[1].store(salt as u32, Ordering::Relaxed);
+}
+
+/// Computes the fingerprint of a value, as printed by [Fingerprint].
+///
+/// This uses SipHash-2-4 keyed with the salt from [set_fingerprint_salt], so it is stable for
+/// equal values within a process (and across processes using the same salt and build).
+pub fn fingerprint<T: Hash + ?Sized>(value: &T) -> u64 {
+    let salt =
+        (SALT[0].load(Ordering::Relaxed) as u64) << 32 | SALT[1].load(Ordering::Relaxed) as u64;
+    keyed_fingerprint(salt, value)
+}
+
+fn keyed_fingerprint<T: Hash + ?Sized>(salt: u64, value: &T) -> u64 {
+    let mut hasher = SipHasher24::new(salt, 0);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Prints a short fingerprint of the value, e.g. `<redacted #a1b2c3d4>`, so that the same value
+/// can be recognised across log lines without revealing it.
+///
+/// See [fingerprint] and [set_fingerprint_salt].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fingerprint;
+
+impl<T: Hash + ?Sized> Msg<T> for Fingerprint {
+    fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        write!(f, "<redacted #{:08x}>", fingerprint(value) >> 32)
+    }
+}
+
+impl<T: Hash + ?Sized> MsgDisplay<T> for Fingerprint {}
+
+/// A streaming implementation of SipHash-2-4.
+///
+/// The std implementation can't be keyed without being deprecated, and isn't guaranteed to stay
+/// the same between releases.
+#[derive(Clone)]
+struct SipHasher24 {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+    tail: u64,
+    length: usize,
+}
+
+impl SipHasher24 {
+    fn new(k0: u64, k1: u64) -> Self {
+        Self {
+            v0: k0 ^ 0x736f6d6570736575,
+            v1: k1 ^ 0x646f72616e646f6d,
+            v2: k0 ^ 0x6c7967656e657261,
+            v3: k1 ^ 0x7465646279746573,
+            tail: 0,
+            length: 0,
+        }
+    }
+
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13) ^ self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16) ^ self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21) ^ self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17) ^ self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    fn compress(&mut self, word: u64) {
+        self.v3 ^= word;
+        self.round();
+        self.round();
+        self.v0 ^= word;
+    }
+}
+
+impl Hasher for SipHasher24 {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.tail |= (*byte as u64) << (8 * (self.length % 8));
+            self.length += 1;
+            if self.length.is_multiple_of(8) {
+                self.compress(self.tail);
+                self.tail = 0;
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = self.clone();
+        state.compress(((self.length as u64 & 0xff) << 56) | self.tail);
+        state.v2 ^= 0xff;
+        for _ in 0..4 {
+            state.round();
+        }
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoDebug;
+    use std::prelude::rust_2021::*;
+
+    fn reference_hash(length: u8) -> u64 {
+        let mut hasher = SipHasher24::new(0x0706050403020100, 0x0f0e0d0c0b0a0908);
+        hasher.write(&(0..length).collect::<Vec<u8>>());
+        hasher.finish()
+    }
+
+    #[test]
+    fn matches_siphash_reference_vectors() {
+        assert_eq!(reference_hash(0), 0x726fdb47dd0e0e31);
+        assert_eq!(reference_hash(1), 0x74f839c593dc67fd);
+        assert_eq!(reference_hash(8), 0x93f5f5799a932462);
+        assert_eq!(reference_hash(15), 0xa129ca6149be45e5);
+    }
+
+    #[test]
+    fn prints_fingerprint() {
+        let value: NoDebug<&str, Fingerprint> = "hunter2".into();
+        let debug = format!("{:?}", value);
+        assert!(debug.starts_with("<redacted #"), "{}", debug);
+        assert_eq!(debug.len(), "<redacted #a1b2c3d4>".len());
+        assert!(!debug.contains("hunter2"));
+        assert_eq!(format!("{}", value), debug);
+    }
+
+    #[test]
+    fn fingerprints_equal_values_equally() {
+        let value: NoDebug<String, Fingerprint> = "hunter2".to_string().into();
+        let other: NoDebug<String, Fingerprint> = "hunter2".to_string().into();
+        assert_eq!(format!("{:?}", value), format!("{:?}", other));
+        assert_eq!(fingerprint(&value), fingerprint(&*other));
+    }
+
+    #[test]
+    fn fingerprints_different_values_differently() {
+        assert_ne!(fingerprint("hunter2"), fingerprint("hunter3"));
+    }
+
+    #[test]
+    fn salt_changes_fingerprints() {
+        assert_ne!(
+            keyed_fingerprint(1, "hunter2"),
+            keyed_fingerprint(2, "hunter2")
+        );
+    }
+}
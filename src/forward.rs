@@ -0,0 +1,309 @@
+//! Forwards common traits from `T` to [NoDebug], so wrapped values can be used in place of `T`.
+//!
+//! All of these keep the [Msg] type, e.g. adding to a `NoDebug<i32, Ellipses>` gives another
+//! `NoDebug<i32, Ellipses>`.
+//!
+//! [NoDebug] forwards [Iterator], so it can't also forward [IntoIterator] for owned values or
+//! mutable references (which is implemented for all iterators). Use `for item in &value`,
+//! `value.iter_mut()` or `value.take()` to iterate over wrapped collections instead.
+
+use crate::{Msg, NoDebug};
+use core::future::Future;
+use core::iter::{FusedIterator, Product, Sum};
+use core::ops::{Index, IndexMut};
+use core::pin::Pin;
+use core::str::FromStr;
+use core::task::{Context, Poll};
+
+impl<T: AsRef<U> + ?Sized, U: ?Sized, M: Msg<T>> AsRef<U> for NoDebug<T, M> {
+    fn as_ref(&self) -> &U {
+        self.as_inner().as_ref()
+    }
+}
+
+impl<T: AsMut<U> + ?Sized, U: ?Sized, M: Msg<T>> AsMut<U> for NoDebug<T, M> {
+    fn as_mut(&mut self) -> &mut U {
+        (**self).as_mut()
+    }
+}
+
+impl<'a, T: ?Sized, M: Msg<T>> IntoIterator for &'a NoDebug<T, M>
+where
+    &'a T: IntoIterator,
+{
+    type Item = <&'a T as IntoIterator>::Item;
+    type IntoIter = <&'a T as IntoIterator>::IntoIter;
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_inner().into_iter()
+    }
+}
+
+impl<T: Iterator, M: Msg<T>> Iterator for NoDebug<T, M> {
+    type Item = T::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        (**self).next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.as_inner().size_hint()
+    }
+}
+
+impl<T: DoubleEndedIterator, M: Msg<T>> DoubleEndedIterator for NoDebug<T, M> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (**self).next_back()
+    }
+}
+
+impl<T: ExactSizeIterator, M: Msg<T>> ExactSizeIterator for NoDebug<T, M> {}
+
+impl<T: FusedIterator, M: Msg<T>> FusedIterator for NoDebug<T, M> {}
+
+impl<T: FromIterator<A>, A, M: Msg<T>> FromIterator<A> for NoDebug<T, M> {
+    fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> Self {
+        Self::wrap(T::from_iter(iter))
+    }
+}
+
+impl<T: Extend<A>, A, M: Msg<T>> Extend<A> for NoDebug<T, M> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        (**self).extend(iter)
+    }
+}
+
+impl<T: Sum<A>, A, M: Msg<T>> Sum<A> for NoDebug<T, M> {
+    fn sum<I: Iterator<Item = A>>(iter: I) -> Self {
+        Self::wrap(T::sum(iter))
+    }
+}
+
+impl<T: Product<A>, A, M: Msg<T>> Product<A> for NoDebug<T, M> {
+    fn product<I: Iterator<Item = A>>(iter: I) -> Self {
+        Self::wrap(T::product(iter))
+    }
+}
+
+impl<T: Index<I> + ?Sized, I, M: Msg<T>> Index<I> for NoDebug<T, M> {
+    type Output = T::Output;
+    fn index(&self, index: I) -> &Self::Output {
+        &self.as_inner()[index]
+    }
+}
+
+impl<T: IndexMut<I> + ?Sized, I, M: Msg<T>> IndexMut<I> for NoDebug<T, M> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut (**self)[index]
+    }
+}
+
+impl<T: FromStr, M: Msg<T>> FromStr for NoDebug<T, M> {
+    type Err = T::Err;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        T::from_str(s).map(Self::wrap)
+    }
+}
+
+impl<T: Future, M: Msg<T>> Future for NoDebug<T, M> {
+    type Output = T::Output;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: The value is structurally pinned. NoDebug has no Drop impl, is only Unpin when
+        // `T` is, and never moves the value out of a pinned reference.
+        unsafe { self.map_unchecked_mut(|value| &mut **value) }.poll(cx)
+    }
+}
+
+/// Forwards binary operators, taking the right hand side as a raw value and keeping the [Msg]
+/// type of the left hand side.
+macro_rules! forward_binary_ops {
+    ($($op: ident::$method: ident, $op_assign: ident::$method_assign: ident;)*) => {
+        $(
+            impl<T: core::ops::$op<Rhs>, Rhs, M: Msg<T> + Msg<T::Output>> core::ops::$op<Rhs>
+                for NoDebug<T, M>
+            {
+                type Output = NoDebug<T::Output, M>;
+                fn $method(self, rhs: Rhs) -> Self::Output {
+                    NoDebug::wrap(self.take().$method(rhs))
+                }
+            }
+
+            impl<T: core::ops::$op_assign<Rhs>, Rhs, M: Msg<T>> core::ops::$op_assign<Rhs>
+                for NoDebug<T, M>
+            {
+                fn $method_assign(&mut self, rhs: Rhs) {
+                    (**self).$method_assign(rhs)
+                }
+            }
+        )*
+    };
+}
+
+forward_binary_ops! {
+    Add::add, AddAssign::add_assign;
+    Sub::sub, SubAssign::sub_assign;
+    Mul::mul, MulAssign::mul_assign;
+    Div::div, DivAssign::div_assign;
+    Rem::rem, RemAssign::rem_assign;
+    BitAnd::bitand, BitAndAssign::bitand_assign;
+    BitOr::bitor, BitOrAssign::bitor_assign;
+    BitXor::bitxor, BitXorAssign::bitxor_assign;
+    Shl::shl, ShlAssign::shl_assign;
+    Shr::shr, ShrAssign::shr_assign;
+}
+
+macro_rules! forward_unary_ops {
+    ($($op: ident::$method: ident;)*) => {
+        $(
+            impl<T: core::ops::$op, M: Msg<T> + Msg<T::Output>> core::ops::$op for NoDebug<T, M> {
+                type Output = NoDebug<T::Output, M>;
+                fn $method(self) -> Self::Output {
+                    NoDebug::wrap(self.take().$method())
+                }
+            }
+        )*
+    };
+}
+
+forward_unary_ops! {
+    Neg::neg;
+    Not::not;
+}
+
+#[cfg(feature = "std")]
+impl<T: std::io::Read, M: Msg<T>> std::io::Read for NoDebug<T, M> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        (**self).read(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: std::io::BufRead, M: Msg<T>> std::io::BufRead for NoDebug<T, M> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        (**self).fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        (**self).consume(amount)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: std::io::Write, M: Msg<T>> std::io::Write for NoDebug<T, M> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        (**self).write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        (**self).flush()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: std::io::Seek, M: Msg<T>> std::io::Seek for NoDebug<T, M> {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        (**self).seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Ellipses, NoDebug};
+    use std::prelude::rust_2021::*;
+
+    #[test]
+    fn forwards_as_ref_and_as_mut() {
+        let mut value: NoDebug<String> = "hunter2".to_string().into();
+        let as_str: &str = value.as_ref();
+        assert_eq!(as_str, "hunter2");
+        let bytes: &[u8] = value.as_ref();
+        assert_eq!(bytes.len(), 7);
+        let as_mut: &mut str = value.as_mut();
+        as_mut.make_ascii_uppercase();
+        assert_eq!(value, "HUNTER2".to_string());
+    }
+
+    #[test]
+    fn iterates_over_references() {
+        let mut value: NoDebug<Vec<i32>, Ellipses> = vec![1, 2, 3].into();
+        for item in value.iter_mut() {
+            *item *= 2;
+        }
+        assert_eq!((&value).into_iter().sum::<i32>(), 12);
+    }
+
+    #[test]
+    fn forwards_iterators() {
+        let mut value: NoDebug<_, Ellipses> = vec![1, 2, 3].into_iter().into();
+        assert_eq!(value.len(), 3);
+        assert_eq!(value.next_back(), Some(3));
+        assert_eq!(value.collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn collects_extends_and_indexes() {
+        let mut value: NoDebug<Vec<i32>, Ellipses> = (1..3).collect();
+        value.extend([3]);
+        value[0] = 4;
+        assert_eq!(value[..], [4, 2, 3]);
+        assert_eq!(format!("{:?}", value), "...");
+    }
+
+    #[test]
+    fn sums_and_multiplies_iterators() {
+        let sum: NoDebug<i32, Ellipses> = [1, 2, 3].into_iter().sum();
+        assert_eq!(sum, 6);
+        let product: NoDebug<i32, Ellipses> = [1, 2, 3, 4].iter().product();
+        assert_eq!(product, 24);
+    }
+
+    #[test]
+    fn parses_from_str() {
+        let value: NoDebug<u16, Ellipses> = "1234".parse().unwrap();
+        assert_eq!(value, 1234);
+        assert!("-1".parse::<NoDebug<u16>>().is_err());
+    }
+
+    #[test]
+    fn forwards_operators_keeping_msg() {
+        let value: NoDebug<i32, Ellipses> = 6.into();
+        let result: NoDebug<i32, Ellipses> = -(value * 7 - 2) % 100;
+        assert_eq!(result, -40);
+        assert_eq!(format!("{:?}", result), "...");
+
+        let mut value: NoDebug<u8> = 0b1010.into();
+        value |= 0b0101;
+        value <<= 1;
+        assert_eq!(value, 0b11110);
+        assert_eq!(!NoDebug::new(true), false);
+    }
+
+    #[test]
+    fn polls_futures() {
+        use core::future::Future;
+        use core::task::{Context, Poll, Waker};
+
+        let mut value: NoDebug<_, Ellipses> = core::future::ready(3).into();
+        let mut cx = Context::from_waker(Waker::noop());
+        assert_eq!(
+            core::pin::Pin::new(&mut value).poll(&mut cx),
+            Poll::Ready(3)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn forwards_io() {
+        use std::io::{BufRead, Read, Write};
+
+        let mut writer: NoDebug<Vec<u8>, Ellipses> = Vec::new().into();
+        writer.write_all(b"line 1\nline 2").unwrap();
+        writer.flush().unwrap();
+
+        let mut reader: NoDebug<_, Ellipses> = std::io::Cursor::new(writer.take()).into();
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "line 1\n");
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "line 2");
+    }
+}
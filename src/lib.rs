@@ -1,109 +1,354 @@
 #![doc = include_str!("../README.md")]
+#![no_std]
 
-use std::fmt::Debug;
-use std::ops::{Deref, DerefMut};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+#[cfg_attr(test, macro_use)]
+extern crate std;
+
+use core::fmt::{Debug, Display};
+use core::ops::{Deref, DerefMut};
+
+#[cfg(feature = "derive")]
+pub use no_debug_derive::RedactedDebug;
+
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+#[macro_use]
+mod context;
+
+#[cfg(feature = "anyhow")]
+pub mod anyhow;
+
+mod error;
+pub use error::RedactedError;
+
+#[cfg(feature = "eyre")]
+pub mod eyre;
+
+mod ext;
+pub use ext::NoDebugExt;
+
+mod fingerprint;
+pub use fingerprint::{fingerprint, set_fingerprint_salt, Fingerprint};
+
+mod forward;
+
+mod max_depth;
+pub use max_depth::MaxDepth;
+
+mod no_debug_ref;
+pub use no_debug_ref::NoDebugRef;
+
+mod partial;
+pub use partial::{Masked, ShowFirst, ShowLast};
+
+#[cfg(feature = "log")]
+mod log_impls;
+
+pub mod policy;
+
+mod reveal;
+pub use reveal::Revealable;
+#[cfg(feature = "reveal")]
+pub use reveal::{is_revealed, reveal_scope, REVEAL_ENV_VAR};
+
+mod sealed;
+pub use sealed::Sealed;
+
+pub mod testing;
+
+mod summary;
+pub use summary::{Summarize, Summary};
+
+mod truncate;
+pub use truncate::Truncate;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "serde")]
+pub use serde_impls::{serialize_redacted, serialize_transparent, MsgSerialize};
+
+#[cfg(feature = "tracing")]
+mod tracing_impls;
+#[cfg(feature = "tracing")]
+pub use tracing_impls::field;
+
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(feature = "zeroize")]
+pub use secret::{CloneableSecret, Secret};
 
 /// [Msg] is a trait for defining custom formatters for [NoDebug] values.
-pub trait Msg<T> {
+pub trait Msg<T: ?Sized> {
     /// Prints a custom message to the given formatter without necessarily revealing the values
     /// information.
     ///
     /// Takes a reference to the value being debugged to allow some introspection.
-    fn fmt(value: &T, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error>;
+    /// Implementations may also write through an adapter around the formatter, e.g. to limit the
+    /// output of the value's own [Debug] impl (see [Truncate]).
+    fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error>;
+
+    /// Whether values may be printed using [Msg::reveal] when reveal mode is enabled by the
+    /// `reveal` feature.
+    ///
+    /// Defaults to `false`, so values are never revealed unless the [Msg] type opts in.
+    const REVEALABLE: bool = false;
+
+    /// Prints the value in full, used in place of [Msg::fmt] in reveal mode if [Msg::REVEALABLE].
+    ///
+    /// Defaults to [Msg::fmt].
+    fn reveal(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        Self::fmt(value, f)
+    }
+}
+
+/// [MsgDisplay] is a trait for [Msg] types that can also be used to [Display] [NoDebug] values.
+///
+/// Custom [Msg] types opt in by implementing this trait, usually without overriding anything.
+pub trait MsgDisplay<T: ?Sized>: Msg<T> {
+    /// Prints a message for `{}` formatting without necessarily revealing the values information.
+    ///
+    /// Defaults to the same message as [Msg::fmt].
+    fn fmt_display(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        <Self as Msg<T>>::fmt(value, f)
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WithTypeInfo;
 
-impl<T> Msg<T> for WithTypeInfo {
-    fn fmt(_value: &T, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        write!(f, "<no debug: {}>", std::any::type_name::<T>())
+impl<T: ?Sized> Msg<T> for WithTypeInfo {
+    fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        write!(f, "<no debug: {}>", core::any::type_name_of_val(value))
     }
 }
 
-#[derive(Debug, Clone)]
+impl<T: ?Sized> MsgDisplay<T> for WithTypeInfo {}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Ellipses;
 
-impl<T> Msg<T> for Ellipses {
-    fn fmt(_value: &T, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+impl<T: ?Sized> Msg<T> for Ellipses {
+    fn fmt(_value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
         write!(f, "...")
     }
 }
 
+impl<T: ?Sized> MsgDisplay<T> for Ellipses {}
+
 /// Wraps a type `T` and provides a [Debug] impl that does not rely on `T` being [Debug].
-#[derive(Eq, Ord, Clone)]
-pub struct NoDebug<T, M: Msg<T> = WithTypeInfo>(T, std::marker::PhantomData<M>);
+///
+/// [NoDebug] has the same layout as `T`, so references and slices can be cast for free (see
+/// [NoDebug::from_ref] and [NoDebug::from_slice]).
+///
+/// Comparison, hashing, [Clone], [Copy] and [Default] only depend on `T`, and `M` never affects
+/// whether [NoDebug] is [Send], [Sync] or [Unpin].
+///
+/// `T` may be unsized, e.g. `&NoDebug<str>` or `Box<NoDebug<[u8]>>` (see [NoDebug::from_ref] and
+/// [NoDebug::from_box]).
+#[repr(transparent)]
+pub struct NoDebug<T: ?Sized, M: Msg<T> = WithTypeInfo>(core::marker::PhantomData<fn() -> M>, T);
+
+impl<T: Clone, M: Msg<T>> Clone for NoDebug<T, M> {
+    fn clone(&self) -> Self {
+        Self::wrap(self.1.clone())
+    }
+}
 
-impl<T: std::hash::Hash, M: Msg<T>> std::hash::Hash for NoDebug<T, M> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.0.hash::<H>(state)
+impl<T: Copy, M: Msg<T>> Copy for NoDebug<T, M> {}
+
+impl<T: Default, M: Msg<T>> Default for NoDebug<T, M> {
+    fn default() -> Self {
+        Self::wrap(T::default())
     }
 }
 
-impl<T: PartialOrd, M: Msg<T>> std::cmp::PartialOrd<T> for NoDebug<T, M> {
-    fn partial_cmp(&self, other: &T) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(other)
+impl<T: core::hash::Hash + ?Sized, M: Msg<T>> core::hash::Hash for NoDebug<T, M> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.1.hash::<H>(state)
     }
 }
 
-impl<T: PartialOrd, M: Msg<T>, N: Msg<T>> std::cmp::PartialOrd<NoDebug<T, N>> for NoDebug<T, M> {
-    fn partial_cmp(&self, other: &NoDebug<T, N>) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(&**other)
+impl<T: Ord + ?Sized, M: Msg<T>> core::cmp::Ord for NoDebug<T, M> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.1.cmp(&other.1)
     }
 }
 
-impl<T: PartialEq, M: Msg<T>> std::cmp::PartialEq<T> for NoDebug<T, M> {
+impl<T: PartialOrd + ?Sized, M: Msg<T>> core::cmp::PartialOrd<T> for NoDebug<T, M> {
+    fn partial_cmp(&self, other: &T) -> Option<core::cmp::Ordering> {
+        self.1.partial_cmp(other)
+    }
+}
+
+impl<T: PartialOrd + ?Sized, M: Msg<T>, N: Msg<T>> core::cmp::PartialOrd<NoDebug<T, N>>
+    for NoDebug<T, M>
+{
+    fn partial_cmp(&self, other: &NoDebug<T, N>) -> Option<core::cmp::Ordering> {
+        self.1.partial_cmp(&other.1)
+    }
+}
+
+impl<T: Eq + ?Sized, M: Msg<T>> core::cmp::Eq for NoDebug<T, M> {}
+
+impl<T: PartialEq + ?Sized, M: Msg<T>> core::cmp::PartialEq<T> for NoDebug<T, M> {
     fn eq(&self, other: &T) -> bool {
-        &self.0 == other
+        &self.1 == other
     }
 }
 
-impl<T: PartialEq, M: Msg<T>, N: Msg<T>> std::cmp::PartialEq<NoDebug<T, N>> for NoDebug<T, M> {
+impl<T: PartialEq + ?Sized, M: Msg<T>, N: Msg<T>> core::cmp::PartialEq<NoDebug<T, N>>
+    for NoDebug<T, M>
+{
     fn eq(&self, other: &NoDebug<T, N>) -> bool {
-        **self == **other
+        self.1 == other.1
+    }
+}
+
+impl<T: ?Sized, M: Msg<T>> NoDebug<T, M> {
+    /// Gets a reference to the value, like [Deref], but usable in `const` items.
+    pub const fn as_inner(&self) -> &T {
+        &self.1
+    }
+
+    /// Borrows the value using another [Msg] type, without moving it.
+    pub const fn as_msg<N: Msg<T>>(&self) -> &NoDebug<T, N> {
+        NoDebug::from_ref(&self.1)
+    }
+
+    /// Mutably borrows the value using another [Msg] type, without moving it.
+    pub fn as_msg_mut<N: Msg<T>>(&mut self) -> &mut NoDebug<T, N> {
+        NoDebug::from_mut(&mut self.1)
+    }
+
+    /// Borrows the value as a [NoDebugRef], keeping the [Msg] type.
+    pub const fn as_no_debug_ref(&self) -> NoDebugRef<'_, T, M> {
+        NoDebugRef::wrap(&self.1)
+    }
+
+    /// Wraps a reference to a value, without moving or cloning it.
+    pub const fn from_ref(value: &T) -> &Self {
+        // SAFETY: NoDebug is `repr(transparent)` over `T`, so the pointers have the same metadata.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+
+    /// Wraps a mutable reference to a value, without moving or cloning it.
+    pub fn from_mut(value: &mut T) -> &mut Self {
+        // SAFETY: NoDebug is `repr(transparent)` over `T`, so the pointers have the same metadata.
+        unsafe { &mut *(value as *mut T as *mut Self) }
+    }
+
+    /// Wraps a boxed value, without moving or cloning it.
+    #[cfg(feature = "alloc")]
+    pub fn from_box(value: alloc::boxed::Box<T>) -> alloc::boxed::Box<Self> {
+        let value = alloc::boxed::Box::into_raw(value);
+        // SAFETY: NoDebug is `repr(transparent)` over `T`, so the pointers have the same metadata
+        // and the box is deallocated with the same layout.
+        unsafe { alloc::boxed::Box::from_raw(value as *mut Self) }
     }
 }
 
 impl<T, M: Msg<T>> NoDebug<T, M> {
+    /// Wraps a value, like [From], but usable in `const` and `static` items with any [Msg] type.
+    pub const fn wrap(value: T) -> Self {
+        Self(core::marker::PhantomData, value)
+    }
+
     pub fn take(self) -> T {
-        self.0
+        self.1
+    }
+
+    /// Changes the [Msg] type used to print the value.
+    pub fn with_msg<N: Msg<T>>(self) -> NoDebug<T, N> {
+        NoDebug::wrap(self.1)
+    }
+
+    /// Wraps each value in a slice, without moving or cloning them.
+    pub const fn from_slice(values: &[T]) -> &[Self] {
+        // SAFETY: NoDebug is `repr(transparent)` over `T`, so the slices have the same layout.
+        unsafe { &*(values as *const [T] as *const [Self]) }
+    }
+
+    /// Wraps each value in a mutable slice, without moving or cloning them.
+    pub fn from_slice_mut(values: &mut [T]) -> &mut [Self] {
+        // SAFETY: NoDebug is `repr(transparent)` over `T`, so the slices have the same layout.
+        unsafe { &mut *(values as *mut [T] as *mut [Self]) }
     }
 }
 
 impl<T> NoDebug<T, WithTypeInfo> {
-    pub fn new(value: T) -> Self {
-        value.into()
+    pub const fn new(value: T) -> Self {
+        Self::wrap(value)
     }
 }
 
 impl<T, M: Msg<T>> From<T> for NoDebug<T, M> {
     fn from(value: T) -> Self {
-        Self(value, std::marker::PhantomData::default())
+        Self::wrap(value)
+    }
+}
+
+impl<'a, T: ?Sized, M: Msg<T>> From<&'a T> for &'a NoDebug<T, M> {
+    fn from(value: &'a T) -> Self {
+        NoDebug::from_ref(value)
+    }
+}
+
+impl<'a, T: ?Sized, M: Msg<T>> From<&'a mut T> for &'a mut NoDebug<T, M> {
+    fn from(value: &'a mut T) -> Self {
+        NoDebug::from_mut(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized, M: Msg<T>> From<alloc::boxed::Box<T>> for alloc::boxed::Box<NoDebug<T, M>> {
+    fn from(value: alloc::boxed::Box<T>) -> Self {
+        NoDebug::from_box(value)
+    }
+}
+
+impl<T: ?Sized, M: Msg<T>> Debug for NoDebug<T, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        reveal::fmt_debug::<T, M>(&self.1, f)
     }
 }
 
-impl<T, M: Msg<T>> Debug for NoDebug<T, M> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        M::fmt(&self.0, f)
+impl<T: ?Sized, M: MsgDisplay<T>> Display for NoDebug<T, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        M::fmt_display(&self.1, f)
     }
 }
 
-impl<T, M: Msg<T>> Deref for NoDebug<T, M> {
+impl<T: ?Sized, M: Msg<T>> Deref for NoDebug<T, M> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.1
     }
 }
 
-impl<T, M: Msg<T>> DerefMut for NoDebug<T, M> {
+impl<T: ?Sized, M: Msg<T>> DerefMut for NoDebug<T, M> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.1
+    }
+}
+
+impl<T: ?Sized, M: Msg<T>> core::borrow::Borrow<T> for NoDebug<T, M> {
+    fn borrow(&self) -> &T {
+        &self.1
+    }
+}
+
+impl<T: ?Sized, M: Msg<T>> core::borrow::BorrowMut<T> for NoDebug<T, M> {
+    fn borrow_mut(&mut self) -> &mut T {
+        &mut self.1
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::prelude::rust_2021::*;
 
     #[test]
     fn cannot_debug_nodebug() {
@@ -129,6 +374,39 @@ mod tests {
         assert_eq!(format!("{:?}", value), "...")
     }
 
+    #[test]
+    fn cannot_display_nodebug() {
+        let value = NoDebug::new("hunter2");
+        assert_eq!(format!("{}", value), "<no debug: &str>")
+    }
+
+    #[test]
+    fn can_display_custom_message() {
+        let value: NoDebug<&str, Ellipses> = "hunter2".into();
+        assert_eq!(format!("{}", value), "...")
+    }
+
+    struct Hidden;
+
+    impl<T> Msg<T> for Hidden {
+        fn fmt(_value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+            write!(f, "<hidden>")
+        }
+    }
+
+    impl<T> MsgDisplay<T> for Hidden {
+        fn fmt_display(_value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+            write!(f, "********")
+        }
+    }
+
+    #[test]
+    fn can_display_with_custom_display_message() {
+        let value: NoDebug<&str, Hidden> = "hunter2".into();
+        assert_eq!(format!("{:?}", value), "<hidden>");
+        assert_eq!(format!("{}", value), "********");
+    }
+
     #[test]
     fn dereferences_nodebug() {
         let value = NoDebug::new(3);
@@ -144,6 +422,75 @@ mod tests {
         assert_eq!(format!("{:?}", *value), "4");
     }
 
+    static API_KEY: NoDebug<&str> = NoDebug::new("hunter2");
+    const TABLE: [NoDebug<&str, Ellipses>; 2] = [NoDebug::wrap("a"), NoDebug::wrap("bc")];
+    const KEY: NoDebug<&str, Hidden> = NoDebug::wrap("hunter2");
+    const KEY_LEN: usize = KEY.as_inner().len();
+
+    #[test]
+    fn can_use_nodebug_in_statics() {
+        assert_eq!(format!("{:?}", API_KEY), "<no debug: &str>");
+        assert_eq!(*API_KEY, "hunter2");
+    }
+
+    #[test]
+    fn can_use_nodebug_in_consts() {
+        assert_eq!(format!("{:?}", TABLE), "[..., ...]");
+        assert_eq!(*TABLE[1].as_inner(), "bc");
+        assert_eq!(format!("{:?}", KEY), "<hidden>");
+        assert_eq!(KEY_LEN, 7);
+    }
+
+    #[test]
+    fn changes_msg() {
+        let value = NoDebug::new(3);
+        let value: NoDebug<i32, Ellipses> = value.with_msg();
+        assert_eq!(format!("{:?}", value), "...");
+        assert_eq!(
+            format!("{:?}", value.as_msg::<WithTypeInfo>()),
+            "<no debug: i32>"
+        );
+    }
+
+    #[test]
+    fn mut_changes_msg() {
+        let mut value = NoDebug::new(3);
+        **value.as_msg_mut::<Ellipses>() += 1;
+        assert_eq!(value, 4);
+    }
+
+    #[test]
+    fn wraps_references() {
+        let value = "hunter2".to_string();
+        let wrapped: &NoDebug<String, Ellipses> = NoDebug::from_ref(&value);
+        assert_eq!(format!("{:?}", wrapped), "...");
+        assert_eq!(**wrapped, "hunter2");
+    }
+
+    #[test]
+    fn wraps_mut_references() {
+        let mut value = "hunter".to_string();
+        let wrapped: &mut NoDebug<String> = NoDebug::from_mut(&mut value);
+        wrapped.push('2');
+        assert_eq!(value, "hunter2");
+    }
+
+    #[test]
+    fn wraps_slices() {
+        let values = vec!["a".to_string(), "b".to_string()];
+        let wrapped: &[NoDebug<String, Ellipses>] = NoDebug::from_slice(&values);
+        assert_eq!(format!("{:?}", wrapped), "[..., ...]");
+        assert_eq!(*wrapped[1], "b");
+    }
+
+    #[test]
+    fn wraps_mut_slices() {
+        let mut values = [1, 2];
+        let wrapped: &mut [NoDebug<i32>] = NoDebug::from_slice_mut(&mut values);
+        *wrapped[0] = 3;
+        assert_eq!(values, [3, 2]);
+    }
+
     #[test]
     fn take_gets_value_from_nodebug() {
         let value = NoDebug::new(3);
@@ -198,10 +545,10 @@ mod tests {
 
     fn get_hash<T>(obj: T) -> u64
     where
-        T: std::hash::Hash,
+        T: core::hash::Hash,
     {
+        use core::hash::Hasher;
         use std::collections::hash_map::DefaultHasher;
-        use std::hash::Hasher;
         let mut hasher = DefaultHasher::new();
         obj.hash(&mut hasher);
         hasher.finish()
@@ -226,4 +573,74 @@ mod tests {
         let other: NoDebug<i32, WithTypeInfo> = 3.into();
         assert_eq!(get_hash(value), get_hash(other));
     }
+
+    /// A [Msg] type implementing no other traits, which shouldn't affect [NoDebug]'s traits.
+    struct Bare(core::marker::PhantomData<*const ()>);
+
+    impl<T> Msg<T> for Bare {
+        fn fmt(_value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+            write!(f, "bare")
+        }
+    }
+
+    #[test]
+    fn can_be_sorted_and_used_as_a_btreemap_key() {
+        let mut values: Vec<NoDebug<String, Bare>> =
+            vec!["b".to_string().into(), "a".to_string().into()];
+        values.sort();
+        assert_eq!(values, ["a".to_string(), "b".to_string()]);
+
+        let map: std::collections::BTreeMap<NoDebug<String, Bare>, i32> =
+            values.into_iter().zip(1..).collect();
+        let key = "b".to_string();
+        assert_eq!(map.get(&key), Some(&2));
+    }
+
+    #[test]
+    fn can_be_looked_up_by_inner_value() {
+        let mut map = std::collections::HashMap::new();
+        map.insert(NoDebug::<String, Bare>::from("key".to_string()), 1);
+        let key = "key".to_string();
+        assert_eq!(map.get(&key), Some(&1));
+    }
+
+    #[test]
+    fn has_copy_and_default_independent_of_msg() {
+        let value: NoDebug<i32, Bare> = NoDebug::default();
+        let copy = value;
+        assert_eq!(value, copy);
+        assert_eq!(value, 0);
+    }
+
+    #[test]
+    fn auto_traits_are_independent_of_msg() {
+        fn assert_auto_traits<T: Send + Sync + Unpin>() {}
+        assert_auto_traits::<NoDebug<i32, Bare>>();
+        assert_auto_traits::<Sealed<i32, Bare>>();
+        assert_auto_traits::<NoDebugRef<'static, i32, Bare>>();
+    }
+
+    #[test]
+    fn wraps_unsized_references() {
+        let value: &NoDebug<str> = "hunter2".into();
+        assert_eq!(format!("{:?}", value), "<no debug: str>");
+        assert_eq!(value, "hunter2");
+        assert_eq!(&value[..6], "hunter");
+
+        let value: &NoDebug<[u8], Summary> = NoDebug::from_ref(&b"hunter2"[..]);
+        assert_eq!(format!("{:?}", value), "<[u8]: 7 items>");
+        assert_eq!(get_hash(value), get_hash(&b"hunter2"[..]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wraps_unsized_boxes() {
+        let value: Box<NoDebug<str, Ellipses>> = Box::<str>::from("hunter2").into();
+        assert_eq!(format!("{:?}", value), "...");
+        assert_eq!(value.len(), 7);
+
+        let value: Box<NoDebug<dyn core::any::Any>> = NoDebug::from_box(Box::new(3));
+        assert_eq!(format!("{:?}", value), "<no debug: dyn core::any::Any>");
+        assert_eq!(value.downcast_ref::<i32>(), Some(&3));
+    }
 }
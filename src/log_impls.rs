@@ -0,0 +1,113 @@
+use crate::{Msg, NoDebug, NoDebugRef};
+use log::kv::{ToValue, Value};
+
+/// Captures [NoDebug] values in `log` key-values using their [Msg] output.
+///
+/// `log` can only capture sized values, so unsized values like `NoDebug<str>` are logged by
+/// borrowing them with [NoDebug::as_no_debug_ref] first.
+/// ```rust
+/// use no_debug::{Ellipses, NoDebug};
+///
+/// let password: &NoDebug<str, Ellipses> = NoDebug::from_ref("hunter2");
+/// log::info!(password = password.as_no_debug_ref(); "logging in");
+/// ```
+impl<T, M: Msg<T>> ToValue for NoDebug<T, M> {
+    fn to_value(&self) -> Value<'_> {
+        Value::from_debug(self)
+    }
+}
+
+/// Captures [NoDebugRef] values in `log` key-values using their [Msg] output.
+impl<T: ?Sized, M: Msg<T>> ToValue for NoDebugRef<'_, T, M> {
+    fn to_value(&self) -> Value<'_> {
+        Value::from_debug(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ellipses;
+    use log::kv::{Error, Key, VisitSource};
+    use log::{Log, Metadata, Record};
+    use std::prelude::rust_2021::*;
+    use std::sync::{Mutex, Once};
+
+    /// Records every log line as `message key=value ...`.
+    struct Capture(Mutex<Vec<String>>);
+
+    static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+    struct Line(String);
+
+    impl<'kvs> VisitSource<'kvs> for Line {
+        fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), Error> {
+            self.0 += &format!(" {}={}", key, value);
+            Ok(())
+        }
+    }
+
+    impl Log for Capture {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            let mut line = Line(record.args().to_string());
+            record.key_values().visit(&mut line).unwrap();
+            self.0.lock().unwrap().push(line.0);
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Gets the captured lines for messages starting with `prefix`.
+    fn captured(prefix: &str) -> Vec<String> {
+        let lines = CAPTURE.0.lock().unwrap();
+        lines
+            .iter()
+            .filter(|line| line.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
+    fn init() {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            log::set_logger(&CAPTURE).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+    }
+
+    #[test]
+    fn logs_redacted_values() {
+        init();
+        let password: NoDebug<&str> = "hunter2".into();
+        log::info!(user = "Cypher1", password = password; "logging in");
+        assert_eq!(
+            captured("logging in"),
+            vec!["logging in user=Cypher1 password=<no debug: &str>"]
+        );
+    }
+
+    #[test]
+    fn never_logs_secrets() {
+        init();
+        let pin: NoDebug<i64, Ellipses> = 1234.into();
+        log::warn!(pin = pin, debug:? = pin; "checking pin");
+        let lines = captured("checking pin");
+        assert_eq!(lines, vec!["checking pin pin=... debug=..."]);
+        assert!(lines.iter().all(|line| !line.contains("1234")));
+    }
+
+    #[test]
+    fn logs_unsized_values() {
+        init();
+        let password: &NoDebug<str, Ellipses> = NoDebug::from_ref("hunter2");
+        log::info!(password = password.as_no_debug_ref(); "unsized login");
+        assert_eq!(
+            captured("unsized login"),
+            vec!["unsized login password=..."]
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use crate::{Msg, MsgDisplay};
+use core::fmt::{Debug, Write};
+
+/// Prints the [Debug] output of the value, collapsing anything nested more than `N` levels deep
+/// into `..`, e.g. `Tree { left: Tree {..}, right: None }`.
+///
+/// Nesting is found from the brackets written by [Debug] impls (like those of `debug_struct`,
+/// `debug_tuple`, `debug_list` and `debug_map`), ignoring any inside string and character literals.
+/// Pretty printing with `{:#?}` is preserved.
+///
+/// This limits the output, not the formatting work: the value's full [Debug] impl still runs and
+/// walks the whole structure, with the collapsed parts being discarded as they are written. It
+/// won't make formatting very large values (e.g. ASTs) faster, use a [Msg] type that doesn't call
+/// [Debug] (like [Summary](crate::Summary)) for that.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MaxDepth<const N: usize>;
+
+impl<T: Debug + ?Sized, const N: usize> Msg<T> for MaxDepth<N> {
+    const REVEALABLE: bool = true;
+
+    fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        let alternate = f.alternate();
+        let mut writer = DepthLimitingWriter {
+            f,
+            max_depth: N,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            literal: None,
+            elided: false,
+        };
+        if alternate {
+            write!(writer, "{:#?}", value)?;
+        } else {
+            write!(writer, "{:?}", value)?;
+        }
+        writer.finish()
+    }
+
+    fn reveal(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        value.fmt(f)
+    }
+}
+
+impl<T: Debug + ?Sized, const N: usize> MsgDisplay<T> for MaxDepth<N> {}
+
+/// The most characters that can follow the opening quote of a character literal, as in
+/// `'\u{10ffff}'`.
+const MAX_LITERAL_LEN: usize = 11;
+
+/// The characters written after a `'` that might open a character literal.
+#[derive(Clone, Copy)]
+struct PendingLiteral {
+    chars: [char; MAX_LITERAL_LEN],
+    len: usize,
+}
+
+/// Whether the characters after a `'` form a character literal.
+enum LiteralState {
+    /// The characters so far could start a character literal.
+    Incomplete,
+    /// The characters end with the closing `'` of a character literal.
+    Complete,
+    /// The `'` doesn't open a character literal, e.g. it is an apostrophe.
+    Invalid,
+}
+
+impl PendingLiteral {
+    const fn new() -> Self {
+        Self {
+            chars: ['\0'; MAX_LITERAL_LEN],
+            len: 0,
+        }
+    }
+
+    fn chars(&self) -> &[char] {
+        &self.chars[..self.len]
+    }
+
+    /// Adds a character, returning whether the characters still form (part of) a literal.
+    fn push(&mut self, c: char) -> LiteralState {
+        self.chars[self.len] = c;
+        self.len += 1;
+        let state = match self.chars() {
+            [] | ['\\'] => LiteralState::Incomplete,
+            ['\''] => LiteralState::Invalid,
+            [_] => LiteralState::Incomplete,
+            ['\\', 'u', rest @ ..] => unicode_escape_state(rest),
+            ['\\', 'n' | 'r' | 't' | '0' | '\\' | '\'' | '"'] => LiteralState::Incomplete,
+            ['\\', 'n' | 'r' | 't' | '0' | '\\' | '\'' | '"', '\''] => LiteralState::Complete,
+            [c, '\''] if *c != '\\' => LiteralState::Complete,
+            _ => LiteralState::Invalid,
+        };
+        // Literals never get longer than the buffer, so anything that would overflow it is
+        // already invalid.
+        debug_assert!(self.len < MAX_LITERAL_LEN || !matches!(state, LiteralState::Incomplete));
+        state
+    }
+}
+
+/// Checks the characters after the `\u` of a unicode escape, e.g. `{1f600}'`.
+fn unicode_escape_state(rest: &[char]) -> LiteralState {
+    let Some((&'{', rest)) = rest.split_first() else {
+        return if rest.is_empty() {
+            LiteralState::Incomplete
+        } else {
+            LiteralState::Invalid
+        };
+    };
+    let digits = rest.iter().take_while(|c| c.is_ascii_hexdigit()).count();
+    match &rest[digits..] {
+        _ if digits > 6 => LiteralState::Invalid,
+        [] => LiteralState::Incomplete,
+        ['}'] if digits > 0 => LiteralState::Incomplete,
+        ['}', '\''] if digits > 0 => LiteralState::Complete,
+        _ => LiteralState::Invalid,
+    }
+}
+
+/// Writes to the formatter, skipping anything nested more than `max_depth` brackets deep.
+struct DepthLimitingWriter<'a, 'b> {
+    f: &'a mut core::fmt::Formatter<'b>,
+    max_depth: usize,
+    depth: usize,
+    /// Whether a string literal is being written.
+    in_string: bool,
+    escaped: bool,
+    /// The characters after a `'` that might open a character literal, held back until it is
+    /// known whether they do.
+    literal: Option<PendingLiteral>,
+    /// Whether anything has been skipped since the last collapsed bracket was opened.
+    elided: bool,
+}
+
+impl DepthLimitingWriter<'_, '_> {
+    fn write_visible(&mut self, c: char) -> Result<(), core::fmt::Error> {
+        if self.depth <= self.max_depth {
+            self.f.write_char(c)
+        } else {
+            self.elided |= !c.is_whitespace();
+            Ok(())
+        }
+    }
+
+    fn push(&mut self, c: char) -> Result<(), core::fmt::Error> {
+        if let Some(mut literal) = self.literal.take() {
+            return match literal.push(c) {
+                LiteralState::Incomplete => {
+                    self.literal = Some(literal);
+                    Ok(())
+                }
+                LiteralState::Complete => {
+                    self.write_visible('\'')?;
+                    literal
+                        .chars()
+                        .iter()
+                        .try_for_each(|&c| self.write_visible(c))
+                }
+                LiteralState::Invalid => self.reject(literal),
+            };
+        }
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if c == '\\' {
+                self.escaped = true;
+            } else if c == '"' {
+                self.in_string = false;
+            }
+            return self.write_visible(c);
+        }
+        match c {
+            '"' => {
+                self.in_string = true;
+                self.write_visible(c)
+            }
+            '\'' => {
+                self.literal = Some(PendingLiteral::new());
+                Ok(())
+            }
+            '{' | '[' | '(' => {
+                self.write_visible(c)?;
+                self.depth += 1;
+                Ok(())
+            }
+            '}' | ']' | ')' => {
+                self.depth = self.depth.saturating_sub(1);
+                if self.depth == self.max_depth && self.elided {
+                    self.f.write_str("..")?;
+                    self.elided = false;
+                }
+                self.write_visible(c)
+            }
+            _ => self.write_visible(c),
+        }
+    }
+
+    /// Writes a `'` that turned out not to open a character literal, then the characters held
+    /// back after it (which may include brackets, or another `'`).
+    fn reject(&mut self, literal: PendingLiteral) -> Result<(), core::fmt::Error> {
+        self.write_visible('\'')?;
+        literal.chars().iter().try_for_each(|&c| self.push(c))
+    }
+
+    /// Writes any characters still held back after the value has been written.
+    fn finish(&mut self) -> Result<(), core::fmt::Error> {
+        while let Some(literal) = self.literal.take() {
+            self.reject(literal)?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for DepthLimitingWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> Result<(), core::fmt::Error> {
+        s.chars().try_for_each(|c| self.push(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoDebug;
+    use std::prelude::rust_2021::*;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Tree {
+        name: &'static str,
+        children: Vec<Tree>,
+    }
+
+    fn tree() -> Tree {
+        Tree {
+            name: "root {",
+            children: vec![
+                Tree {
+                    name: "a",
+                    children: vec![Tree {
+                        name: "b",
+                        children: vec![],
+                    }],
+                },
+                Tree {
+                    name: "c",
+                    children: vec![],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn collapses_deep_values() {
+        let value: NoDebug<Tree, MaxDepth<2>> = tree().into();
+        assert_eq!(
+            format!("{:?}", value),
+            r#"Tree { name: "root {", children: [Tree {..}, Tree {..}] }"#
+        );
+        let value: NoDebug<Tree, MaxDepth<3>> = tree().into();
+        assert_eq!(
+            format!("{:?}", value),
+            r#"Tree { name: "root {", children: [Tree { name: "a", children: [..] }, Tree { name: "c", children: [] }] }"#
+        );
+    }
+
+    #[test]
+    fn collapses_everything_at_depth_zero() {
+        let value: NoDebug<Tree, MaxDepth<0>> = tree().into();
+        assert_eq!(format!("{:?}", value), "Tree {..}");
+        assert_eq!(format!("{}", value), "Tree {..}");
+    }
+
+    #[test]
+    fn does_not_collapse_shallow_values() {
+        let value: NoDebug<Option<(char, &str)>, MaxDepth<2>> = Some((')', "\"]")).into();
+        assert_eq!(format!("{:?}", value), r#"Some((')', "\"]"))"#);
+    }
+
+    /// Writes unquoted text containing apostrophes, like some hand-written [Debug] impls.
+    struct Named(&'static str, Option<Box<Named>>);
+
+    impl Debug for Named {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+            let mut f = f.debug_struct(self.0);
+            if let Some(child) = &self.1 {
+                f.field("child", child);
+            }
+            f.finish()
+        }
+    }
+
+    #[test]
+    fn collapses_after_apostrophes() {
+        let leaf = Named("Carol's leaf", None);
+        let child = Named("Alice's tree", Some(Box::new(leaf)));
+        let value: NoDebug<Named, MaxDepth<1>> = Named("Bob's tree", Some(Box::new(child))).into();
+        assert_eq!(
+            format!("{:?}", value),
+            "Bob's tree { child: Alice's tree {..} }"
+        );
+        let value: NoDebug<Named, MaxDepth<0>> = Named("trailing'", None).into();
+        assert_eq!(format!("{:?}", value), "trailing'");
+    }
+
+    #[test]
+    fn ignores_brackets_in_escaped_chars() {
+        let value: NoDebug<[char; 4], MaxDepth<1>> = ['{', '\'', '\u{301}', '\\'].into();
+        assert_eq!(format!("{:?}", value), r"['{', '\'', '\u{301}', '\\']");
+    }
+
+    #[test]
+    fn preserves_pretty_printing() {
+        let value: NoDebug<Tree, MaxDepth<2>> = tree().into();
+        assert_eq!(
+            format!("{:#?}", value),
+            r#"Tree {
+    name: "root {",
+    children: [
+        Tree {..},
+        Tree {..},
+    ],
+}"#
+        );
+    }
+}
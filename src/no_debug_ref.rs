@@ -0,0 +1,103 @@
+use crate::{reveal, Msg, MsgDisplay, WithTypeInfo};
+use core::fmt::{Debug, Display};
+
+/// Borrows a value of type `T` and provides a [Debug] impl that does not rely on `T` being
+/// [Debug], like [NoDebug](crate::NoDebug) does for owned values.
+///
+/// This allows printing values without changing their type, usually via
+/// [NoDebugExt](crate::NoDebugExt).
+pub struct NoDebugRef<'a, T: ?Sized, M: Msg<T> = WithTypeInfo>(
+    &'a T,
+    core::marker::PhantomData<fn() -> M>,
+);
+
+impl<'a, T: ?Sized, M: Msg<T>> NoDebugRef<'a, T, M> {
+    /// Borrows a value, like [From], but usable in `const` items with any [Msg] type.
+    pub const fn wrap(value: &'a T) -> Self {
+        Self(value, core::marker::PhantomData)
+    }
+
+    /// Gets the borrowed value.
+    pub const fn get(&self) -> &'a T {
+        self.0
+    }
+}
+
+impl<'a, T: ?Sized> NoDebugRef<'a, T, WithTypeInfo> {
+    pub const fn new(value: &'a T) -> Self {
+        Self::wrap(value)
+    }
+}
+
+impl<'a, T: ?Sized, M: Msg<T>> From<&'a T> for NoDebugRef<'a, T, M> {
+    fn from(value: &'a T) -> Self {
+        Self::wrap(value)
+    }
+}
+
+impl<T: ?Sized, M: Msg<T>> Clone for NoDebugRef<'_, T, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized, M: Msg<T>> Copy for NoDebugRef<'_, T, M> {}
+
+impl<T: ?Sized, M: Msg<T>> Debug for NoDebugRef<'_, T, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        reveal::fmt_debug::<T, M>(self.0, f)
+    }
+}
+
+impl<T: ?Sized, M: MsgDisplay<T>> Display for NoDebugRef<'_, T, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        M::fmt_display(self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ellipses;
+    use std::prelude::rust_2021::*;
+
+    struct User {
+        name: &'static str,
+        password: String,
+    }
+
+    #[test]
+    fn cannot_debug_nodebugref() {
+        let value = 3;
+        assert_eq!(format!("{:?}", NoDebugRef::new(&value)), "<no debug: i32>");
+        assert_eq!(format!("{}", NoDebugRef::new(&value)), "<no debug: i32>");
+    }
+
+    #[test]
+    fn can_show_custom_message() {
+        let value: NoDebugRef<i32, Ellipses> = (&3).into();
+        assert_eq!(format!("{:?}", value), "...");
+    }
+
+    #[test]
+    fn redacts_fields_without_changing_types() {
+        let user = User {
+            name: "Cypher1",
+            password: "hunter2".to_string(),
+        };
+        let debug = format!(
+            "{:?}",
+            (user.name, NoDebugRef::<_, Ellipses>::wrap(&user.password))
+        );
+        assert_eq!(debug, r#"("Cypher1", ...)"#);
+    }
+
+    #[test]
+    fn gets_borrowed_value() {
+        let value = 3;
+        let wrapped = NoDebugRef::<i32>::new(&value);
+        let copied = wrapped;
+        assert_eq!(*wrapped.get(), 3);
+        assert_eq!(*copied.get(), 3);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{Msg, MsgDisplay};
+use core::fmt::Write;
+
+/// Shows the last `N` characters of a string, replacing the others with `C`, e.g. `****abcd`.
+///
+/// Strings with `N` or fewer characters are masked entirely.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShowLast<const N: usize, const C: char = '*'>;
+
+impl<T: AsRef<str> + ?Sized, const N: usize, const C: char> Msg<T> for ShowLast<N, C> {
+    fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        let value = value.as_ref();
+        let hidden = value.chars().count().saturating_sub(N);
+        if hidden == 0 {
+            return Masked::<C>::fmt(&value, f);
+        }
+        for (index, c) in value.chars().enumerate() {
+            f.write_char(if index < hidden { C } else { c })?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: AsRef<str> + ?Sized, const N: usize, const C: char> MsgDisplay<T> for ShowLast<N, C> {}
+
+/// Shows the first `N` characters of a string, replacing the others with `C`, e.g. `abcd****`.
+///
+/// Strings with `N` or fewer characters are masked entirely.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShowFirst<const N: usize, const C: char = '*'>;
+
+impl<T: AsRef<str> + ?Sized, const N: usize, const C: char> Msg<T> for ShowFirst<N, C> {
+    fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        let value = value.as_ref();
+        if value.chars().count() <= N {
+            return Masked::<C>::fmt(&value, f);
+        }
+        for (index, c) in value.chars().enumerate() {
+            f.write_char(if index < N { c } else { C })?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: AsRef<str> + ?Sized, const N: usize, const C: char> MsgDisplay<T> for ShowFirst<N, C> {}
+
+/// Replaces every character of a string with `C`, e.g. `********`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Masked<const C: char = '*'>;
+
+impl<T: AsRef<str> + ?Sized, const C: char> Msg<T> for Masked<C> {
+    fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        for _ in value.as_ref().chars() {
+            f.write_char(C)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: AsRef<str> + ?Sized, const C: char> MsgDisplay<T> for Masked<C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoDebug;
+    use std::prelude::rust_2021::*;
+
+    #[test]
+    fn shows_last_characters() {
+        let value: NoDebug<String, ShowLast<4>> = "sk_live_abcd".to_string().into();
+        assert_eq!(format!("{:?}", value), "********abcd");
+        assert_eq!(format!("{}", value), "********abcd");
+    }
+
+    #[test]
+    fn shows_first_characters() {
+        let value: NoDebug<&str, ShowFirst<3>> = "sk_live_abcd".into();
+        assert_eq!(format!("{:?}", value), "sk_*********");
+    }
+
+    #[test]
+    fn masks_every_character() {
+        let value: NoDebug<&str, Masked> = "hunter2".into();
+        assert_eq!(format!("{:?}", value), "*******");
+    }
+
+    #[test]
+    fn uses_custom_mask_character() {
+        let value: NoDebug<&str, ShowLast<4, '#'>> = "4242424242424242".into();
+        assert_eq!(format!("{:?}", value), "############4242");
+        let value: NoDebug<&str, Masked<'x'>> = "hunter2".into();
+        assert_eq!(format!("{:?}", value), "xxxxxxx");
+    }
+
+    #[test]
+    fn masks_short_values_entirely() {
+        let value: NoDebug<&str, ShowLast<4>> = "abcd".into();
+        assert_eq!(format!("{:?}", value), "****");
+        let value: NoDebug<&str, ShowFirst<4>> = "abc".into();
+        assert_eq!(format!("{:?}", value), "***");
+    }
+
+    #[test]
+    fn counts_characters_rather_than_bytes() {
+        let value: NoDebug<&str, ShowLast<2>> = "héllo wörld".into();
+        assert_eq!(format!("{:?}", value), "*********ld");
+        let value: NoDebug<&str, ShowFirst<2>> = "héllo".into();
+        assert_eq!(format!("{:?}", value), "hé***");
+    }
+}
@@ -0,0 +1,266 @@
+//! Classifies values by [Sensitivity], with a process-wide [Policy] deciding how each level is
+//! printed.
+//!
+//! The [Public], [Internal], [Pii] and [Secret] [Msg] types each carry a [Sensitivity] (see
+//! [Level]), and print values according to the [Treatment] the current [Policy] gives their level.
+//! ```rust
+//! use no_debug::policy::{self, Pii, Policy, Treatment};
+//! use no_debug::NoDebug;
+//!
+//! let email: NoDebug<String, Pii> = "user@example.com".to_string().into();
+//! assert!(format!("{:?}", email).starts_with("<redacted #"));
+//!
+//! policy::set_policy(Policy {
+//!     pii: Treatment::Hide,
+//!     ..Policy::default()
+//! });
+//! assert_eq!(format!("{:?}", email), "...");
+//! # policy::set_policy(Policy::default());
+//! ```
+
+use crate::{Ellipses, Fingerprint, Msg, MsgDisplay, WithTypeInfo};
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// How sensitive a value is, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Sensitivity {
+    /// Values that are safe to print anywhere.
+    Public,
+    /// Values that aren't private, but are verbose or only of interest internally.
+    Internal,
+    /// Personally identifiable information.
+    Pii,
+    /// Credentials and other values that must never be printed.
+    Secret,
+}
+
+/// How values are printed, and whether they are serialized.
+///
+/// With the `serde` feature, values are serialized as they are when shown, and replaced by the
+/// printed message otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Treatment {
+    /// Prints the value using its own [Debug] impl.
+    Show,
+    /// Prints the type of the value, like [WithTypeInfo].
+    TypeName,
+    /// Prints a fingerprint of the value, like [Fingerprint].
+    Fingerprint,
+    /// Prints nothing about the value, like [Ellipses].
+    Hide,
+}
+
+impl Treatment {
+    const ALL: [Treatment; 4] = [
+        Treatment::Show,
+        Treatment::TypeName,
+        Treatment::Fingerprint,
+        Treatment::Hide,
+    ];
+}
+
+/// Decides the [Treatment] for each [Sensitivity].
+///
+/// [Treatment::Show] is never applied to [Sensitivity::Secret] values, which are hidden instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Policy {
+    pub public: Treatment,
+    pub internal: Treatment,
+    pub pii: Treatment,
+    pub secret: Treatment,
+}
+
+impl Policy {
+    /// Shows public values, prints the type of internal values, fingerprints PII and hides secrets.
+    pub const DEFAULT: Policy = Policy {
+        public: Treatment::Show,
+        internal: Treatment::TypeName,
+        pii: Treatment::Fingerprint,
+        secret: Treatment::Hide,
+    };
+
+    pub fn treatment(&self, sensitivity: Sensitivity) -> Treatment {
+        match sensitivity {
+            Sensitivity::Public => self.public,
+            Sensitivity::Internal => self.internal,
+            Sensitivity::Pii => self.pii,
+            Sensitivity::Secret if self.secret == Treatment::Show => Treatment::Hide,
+            Sensitivity::Secret => self.secret,
+        }
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static POLICY: [AtomicU8; 4] = [
+    AtomicU8::new(Policy::DEFAULT.public as u8),
+    AtomicU8::new(Policy::DEFAULT.internal as u8),
+    AtomicU8::new(Policy::DEFAULT.pii as u8),
+    AtomicU8::new(Policy::DEFAULT.secret as u8),
+];
+
+fn load(sensitivity: Sensitivity) -> Treatment {
+    Treatment::ALL[POLICY[sensitivity as usize].load(Ordering::Relaxed) as usize]
+}
+
+/// Sets the [Policy] used by this process.
+pub fn set_policy(policy: Policy) {
+    POLICY[Sensitivity::Public as usize].store(policy.public as u8, Ordering::Relaxed);
+    POLICY[Sensitivity::Internal as usize].store(policy.internal as u8, Ordering::Relaxed);
+    POLICY[Sensitivity::Pii as usize].store(policy.pii as u8, Ordering::Relaxed);
+    POLICY[Sensitivity::Secret as usize].store(policy.secret as u8, Ordering::Relaxed);
+}
+
+/// Gets the [Policy] used by this process.
+pub fn policy() -> Policy {
+    Policy {
+        public: load(Sensitivity::Public),
+        internal: load(Sensitivity::Internal),
+        pii: load(Sensitivity::Pii),
+        secret: load(Sensitivity::Secret),
+    }
+}
+
+/// Runs `f` with the given [Policy], holding a lock so that tests using the process-wide policy
+/// don't race with each other.
+#[cfg(test)]
+pub(crate) fn with_policy<R>(policy: Policy, f: impl FnOnce() -> R) -> R {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    /// Restores the default policy, even if `f` panics.
+    struct Reset;
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            set_policy(Policy::default());
+        }
+    }
+
+    let _lock = LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let _reset = Reset;
+    set_policy(policy);
+    f()
+}
+
+/// [Level] is a trait for [Msg] types that classify values with a [Sensitivity].
+pub trait Level {
+    const SENSITIVITY: Sensitivity;
+}
+
+fn fmt_with_policy<T: Debug + Hash + ?Sized>(
+    sensitivity: Sensitivity,
+    value: &T,
+    f: &mut core::fmt::Formatter,
+) -> Result<(), core::fmt::Error> {
+    match policy().treatment(sensitivity) {
+        Treatment::Show => value.fmt(f),
+        Treatment::TypeName => <WithTypeInfo as Msg<T>>::fmt(value, f),
+        Treatment::Fingerprint => <Fingerprint as Msg<T>>::fmt(value, f),
+        Treatment::Hide => <Ellipses as Msg<T>>::fmt(value, f),
+    }
+}
+
+macro_rules! level {
+    ($(#[$attr: meta])* $name: ident, $sensitivity: ident, revealable: $revealable: literal) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name;
+
+        impl Level for $name {
+            const SENSITIVITY: Sensitivity = Sensitivity::$sensitivity;
+        }
+
+        impl<T: Debug + Hash + ?Sized> Msg<T> for $name {
+            const REVEALABLE: bool = $revealable;
+
+            fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+                fmt_with_policy(Self::SENSITIVITY, value, f)
+            }
+
+            fn reveal(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+                value.fmt(f)
+            }
+        }
+
+        impl<T: Debug + Hash + ?Sized> MsgDisplay<T> for $name {}
+    };
+}
+
+level!(
+    /// Classifies values as [Sensitivity::Public].
+    Public,
+    Public,
+    revealable: true
+);
+level!(
+    /// Classifies values as [Sensitivity::Internal].
+    Internal,
+    Internal,
+    revealable: true
+);
+level!(
+    /// Classifies values as [Sensitivity::Pii].
+    Pii,
+    Pii,
+    revealable: false
+);
+level!(
+    /// Classifies values as [Sensitivity::Secret].
+    Secret,
+    Secret,
+    revealable: false
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoDebug;
+    use std::prelude::rust_2021::*;
+
+    #[test]
+    fn secrets_are_never_shown() {
+        let policy = Policy {
+            secret: Treatment::Show,
+            ..Policy::default()
+        };
+        assert_eq!(policy.treatment(Sensitivity::Secret), Treatment::Hide);
+    }
+
+    #[test]
+    fn applies_policy_to_levels() {
+        let public: NoDebug<i32, Public> = 3.into();
+        let internal: NoDebug<i32, Internal> = 3.into();
+        let pii: NoDebug<i32, Pii> = 3.into();
+        let secret: NoDebug<i32, Secret> = 3.into();
+
+        with_policy(Policy::default(), || {
+            assert_eq!(policy(), Policy::default());
+            assert_eq!(format!("{:?}", public), "3");
+            assert_eq!(format!("{:?}", internal), "<no debug: i32>");
+            assert!(format!("{:?}", pii).starts_with("<redacted #"));
+            assert_eq!(format!("{}", secret), "...");
+        });
+
+        let custom = Policy {
+            public: Treatment::TypeName,
+            internal: Treatment::Show,
+            pii: Treatment::Hide,
+            secret: Treatment::Fingerprint,
+        };
+        with_policy(custom, || {
+            assert_eq!(policy(), custom);
+            assert_eq!(format!("{:?}", public), "<no debug: i32>");
+            assert_eq!(format!("{:?}", internal), "3");
+            assert_eq!(format!("{:?}", pii), "...");
+            assert!(format!("{:?}", secret).starts_with("<redacted #"));
+        });
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{Msg, MsgDisplay};
+use core::fmt::Debug;
+
+/// Wraps another [Msg] type `M`, making values revealable when reveal mode is enabled.
+///
+/// Otherwise values are printed using `M`, e.g. `NoDebug<Vec<String>, Revealable<Ellipses>>`
+/// prints `...` unless revealed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Revealable<M = crate::WithTypeInfo>(core::marker::PhantomData<fn() -> M>);
+
+impl<T: Debug + ?Sized, M: Msg<T>> Msg<T> for Revealable<M> {
+    const REVEALABLE: bool = true;
+
+    fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        M::fmt(value, f)
+    }
+
+    fn reveal(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        value.fmt(f)
+    }
+}
+
+impl<T: Debug + ?Sized, M: MsgDisplay<T>> MsgDisplay<T> for Revealable<M> {
+    fn fmt_display(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        M::fmt_display(value, f)
+    }
+}
+
+/// Formats a value for [Debug] using `M`, revealing it if reveal mode is enabled and `M` allows it.
+pub(crate) fn fmt_debug<T: ?Sized, M: Msg<T>>(
+    value: &T,
+    f: &mut core::fmt::Formatter,
+) -> Result<(), core::fmt::Error> {
+    #[cfg(feature = "reveal")]
+    if M::REVEALABLE && is_revealed() {
+        return M::reveal(value, f);
+    }
+    M::fmt(value, f)
+}
+
+#[cfg(feature = "reveal")]
+std::thread_local! {
+    static REVEAL_SCOPES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// The environment variable that enables reveal mode for the whole process when set to `1` or
+/// `true`.
+#[cfg(feature = "reveal")]
+pub const REVEAL_ENV_VAR: &str = "NO_DEBUG_REVEAL";
+
+/// Runs `f` with reveal mode enabled on the current thread, so that [Debug] for `NoDebug` values
+/// uses the value's own [Debug] impl if their [Msg] type is [Msg::REVEALABLE].
+#[cfg(feature = "reveal")]
+pub fn reveal_scope<R>(f: impl FnOnce() -> R) -> R {
+    struct Guard;
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            REVEAL_SCOPES.with(|scopes| scopes.set(scopes.get() - 1));
+        }
+    }
+
+    REVEAL_SCOPES.with(|scopes| scopes.set(scopes.get() + 1));
+    let _guard = Guard;
+    f()
+}
+
+/// Whether reveal mode is enabled, either by [reveal_scope] or the [REVEAL_ENV_VAR] environment
+/// variable.
+#[cfg(feature = "reveal")]
+pub fn is_revealed() -> bool {
+    in_reveal_scope() || revealed_by_env()
+}
+
+/// Whether [REVEAL_ENV_VAR] enables reveal mode, which is ignored by unit tests so that their
+/// output doesn't depend on the environment they run in.
+#[cfg(all(feature = "reveal", not(test)))]
+fn revealed_by_env() -> bool {
+    static FROM_ENV: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *FROM_ENV.get_or_init(|| {
+        std::env::var(REVEAL_ENV_VAR).is_ok_and(|value| value == "1" || value == "true")
+    })
+}
+
+#[cfg(all(feature = "reveal", test))]
+fn revealed_by_env() -> bool {
+    false
+}
+
+/// Whether the current thread is running inside a [reveal_scope].
+#[cfg(feature = "reveal")]
+fn in_reveal_scope() -> bool {
+    REVEAL_SCOPES.with(|scopes| scopes.get() > 0)
+}
+
+#[cfg(all(test, feature = "reveal"))]
+mod tests {
+    use super::*;
+    use crate::{Ellipses, Fingerprint, NoDebug, Truncate};
+    use std::prelude::rust_2021::*;
+
+    #[test]
+    fn hides_values_outside_reveal_scope() {
+        let value: NoDebug<i32, Revealable<Ellipses>> = 3.into();
+        assert!(!is_revealed());
+        assert_eq!(format!("{:?}", value), "...");
+    }
+
+    #[test]
+    fn reveals_values_in_reveal_scope() {
+        let value: NoDebug<i32, Revealable<Ellipses>> = 3.into();
+        reveal_scope(|| {
+            assert!(is_revealed());
+            assert_eq!(format!("{:?}", value), "3");
+        });
+        assert!(!in_reveal_scope());
+    }
+
+    #[test]
+    fn reveals_sealed_values() {
+        let value: crate::Sealed<i32, Revealable<Ellipses>> = 3.into();
+        assert_eq!(reveal_scope(|| format!("{:?}", value)), "3");
+        let value: crate::Sealed<i32, Ellipses> = 3.into();
+        assert_eq!(reveal_scope(|| format!("{:?}", value)), "...");
+    }
+
+    #[test]
+    fn reveals_revealable_built_in_msgs() {
+        let value: NoDebug<Vec<i32>, Truncate<2>> = vec![1, 2, 3].into();
+        assert_eq!(reveal_scope(|| format!("{:?}", value)), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn never_reveals_secrets() {
+        let value: NoDebug<&str, Fingerprint> = "hunter2".into();
+        let revealed = reveal_scope(|| format!("{:?}", value));
+        assert!(!revealed.contains("hunter2"));
+        let value: NoDebug<&str> = "hunter2".into();
+        let revealed = reveal_scope(|| format!("{:?}", value));
+        assert_eq!(revealed, "<no debug: &str>");
+    }
+
+    #[test]
+    fn nested_reveal_scopes_stay_revealed() {
+        reveal_scope(|| {
+            reveal_scope(|| assert!(in_reveal_scope()));
+            assert!(in_reveal_scope());
+        });
+        assert!(!in_reveal_scope());
+    }
+
+    #[test]
+    fn does_not_reveal_display() {
+        let value: NoDebug<i32, Revealable<Ellipses>> = 3.into();
+        assert_eq!(reveal_scope(|| format!("{}", value)), "...");
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{reveal, Msg, MsgDisplay, WithTypeInfo};
+use core::fmt::{Debug, Display};
+
+/// Wraps a type `T` like [NoDebug](crate::NoDebug), but without `Deref` or `DerefMut`.
+///
+/// The value can only be accessed via [Sealed::expose], [Sealed::expose_mut],
+/// [Sealed::with_exposed] or [Sealed::take], which are easy to search for when auditing where
+/// sensitive values are used.
+///
+/// Like [NoDebug](crate::NoDebug), trait impls only depend on `T` and never on `M`.
+pub struct Sealed<T, M: Msg<T> = WithTypeInfo>(T, core::marker::PhantomData<fn() -> M>);
+
+impl<T: Clone, M: Msg<T>> Clone for Sealed<T, M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), core::marker::PhantomData)
+    }
+}
+
+impl<T: Copy, M: Msg<T>> Copy for Sealed<T, M> {}
+
+impl<T: Default, M: Msg<T>> Default for Sealed<T, M> {
+    fn default() -> Self {
+        Self(T::default(), core::marker::PhantomData)
+    }
+}
+
+impl<T: core::hash::Hash, M: Msg<T>> core::hash::Hash for Sealed<T, M> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash::<H>(state)
+    }
+}
+
+impl<T: Ord, M: Msg<T>> core::cmp::Ord for Sealed<T, M> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: PartialOrd, M: Msg<T>> core::cmp::PartialOrd<T> for Sealed<T, M> {
+    fn partial_cmp(&self, other: &T) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
+impl<T: PartialOrd, M: Msg<T>, N: Msg<T>> core::cmp::PartialOrd<Sealed<T, N>> for Sealed<T, M> {
+    fn partial_cmp(&self, other: &Sealed<T, N>) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<T: Eq, M: Msg<T>> core::cmp::Eq for Sealed<T, M> {}
+
+impl<T: PartialEq, M: Msg<T>> core::cmp::PartialEq<T> for Sealed<T, M> {
+    fn eq(&self, other: &T) -> bool {
+        &self.0 == other
+    }
+}
+
+impl<T: PartialEq, M: Msg<T>, N: Msg<T>> core::cmp::PartialEq<Sealed<T, N>> for Sealed<T, M> {
+    fn eq(&self, other: &Sealed<T, N>) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T, M: Msg<T>> Sealed<T, M> {
+    pub fn take(self) -> T {
+        self.0
+    }
+
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    pub fn expose_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// Runs `f` with a reference to the value, returning its result.
+    pub fn with_exposed<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.0)
+    }
+
+    /// Runs `f` with a mutable reference to the value, returning its result.
+    pub fn with_exposed_mut<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.0)
+    }
+}
+
+impl<T> Sealed<T, WithTypeInfo> {
+    pub fn new(value: T) -> Self {
+        value.into()
+    }
+}
+
+impl<T, M: Msg<T>> From<T> for Sealed<T, M> {
+    fn from(value: T) -> Self {
+        Self(value, core::marker::PhantomData)
+    }
+}
+
+impl<T, M: Msg<T>> Debug for Sealed<T, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        reveal::fmt_debug::<T, M>(&self.0, f)
+    }
+}
+
+impl<T, M: MsgDisplay<T>> Display for Sealed<T, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        M::fmt_display(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ellipses;
+    use std::prelude::rust_2021::*;
+
+    #[test]
+    fn cannot_debug_sealed() {
+        let value = Sealed::new(3);
+        assert_eq!(format!("{:?}", value), "<no debug: i32>");
+        assert_eq!(format!("{}", value), "<no debug: i32>");
+    }
+
+    #[test]
+    fn can_show_custom_message() {
+        let value: Sealed<i32, Ellipses> = 3.into();
+        assert_eq!(format!("{:?}", value), "...");
+    }
+
+    #[test]
+    fn exposes_sealed() {
+        let value = Sealed::new(3);
+        assert_eq!(format!("{:?}", value.expose()), "3");
+    }
+
+    #[test]
+    fn mut_exposes_sealed() {
+        let mut value = Sealed::new(3);
+        *value.expose_mut() = 4;
+        assert_eq!(value.take(), 4);
+    }
+
+    #[test]
+    fn with_exposed_runs_on_sealed_value() {
+        let mut value = Sealed::new(3);
+        value.with_exposed_mut(|value| *value += 1);
+        assert_eq!(value.with_exposed(|value| value * 2), 8);
+    }
+
+    #[test]
+    fn take_gets_value_from_sealed() {
+        let value = Sealed::new(3);
+        assert_eq!(value.take(), 3);
+    }
+
+    #[test]
+    fn has_eq_with_raw_value() {
+        let value = Sealed::new(3);
+        assert_eq!(value, 3);
+    }
+
+    #[test]
+    fn has_eq_with_another_sealed_with_different_msg() {
+        let value: Sealed<i32, Ellipses> = 3.into();
+        let other: Sealed<i32, WithTypeInfo> = 3.into();
+        assert_eq!(value, other);
+    }
+
+    #[test]
+    fn has_ord_with_raw_value() {
+        let value = Sealed::new(2);
+        assert!(value < 3);
+    }
+
+    #[test]
+    fn has_ord_with_another_sealed_with_different_msg() {
+        let value: Sealed<i32, Ellipses> = 2.into();
+        let other: Sealed<i32, WithTypeInfo> = 3.into();
+        assert!(value < other);
+    }
+
+    fn get_hash<T>(obj: T) -> u64
+    where
+        T: core::hash::Hash,
+    {
+        use core::hash::Hasher;
+        use std::collections::hash_map::DefaultHasher;
+        let mut hasher = DefaultHasher::new();
+        obj.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn has_hash_with_raw_value() {
+        let value: Sealed<i32> = 3.into();
+        assert_eq!(get_hash(value), get_hash(3));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{reveal, Msg, MsgDisplay, NoDebug, WithTypeInfo};
+use core::fmt::{Debug, Display};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Marks secret types that may be cloned while wrapped in a [Secret].
+///
+/// Every clone is another copy of the secret in memory, so this is opt in.
+pub trait CloneableSecret: Clone + Zeroize {}
+
+/// Wraps a secret `T`, formatting it with `M` like [NoDebug] and zeroizing it when dropped.
+///
+/// Unlike [NoDebug], [Secret] does not implement `Deref`, so the value can only be accessed via
+/// [Secret::expose] and [Secret::expose_mut].
+pub struct Secret<T: Zeroize, M: Msg<T> = WithTypeInfo>(T, core::marker::PhantomData<fn() -> M>);
+
+impl<T: Zeroize, M: Msg<T>> Secret<T, M> {
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    pub fn expose_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Zeroize> Secret<T, WithTypeInfo> {
+    pub fn new(value: T) -> Self {
+        value.into()
+    }
+}
+
+impl<T: Zeroize, M: Msg<T>> From<T> for Secret<T, M> {
+    fn from(value: T) -> Self {
+        Self(value, core::marker::PhantomData)
+    }
+}
+
+impl<T: CloneableSecret, M: Msg<T>> Clone for Secret<T, M> {
+    fn clone(&self) -> Self {
+        self.0.clone().into()
+    }
+}
+
+impl<T: Zeroize, M: Msg<T>> Debug for Secret<T, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        reveal::fmt_debug::<T, M>(&self.0, f)
+    }
+}
+
+impl<T: Zeroize, M: MsgDisplay<T>> Display for Secret<T, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        M::fmt_display(&self.0, f)
+    }
+}
+
+impl<T: Zeroize, M: Msg<T>> Drop for Secret<T, M> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize, M: Msg<T>> ZeroizeOnDrop for Secret<T, M> {}
+
+impl<T: Zeroize + ?Sized, M: Msg<T>> Zeroize for NoDebug<T, M> {
+    fn zeroize(&mut self) {
+        (**self).zeroize();
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::Ellipses;
+    use std::cell::Cell;
+    use std::prelude::rust_2021::*;
+    use std::rc::Rc;
+
+    #[derive(Clone)]
+    struct Tracked(Rc<Cell<bool>>);
+
+    impl Zeroize for Tracked {
+        fn zeroize(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    impl CloneableSecret for Tracked {}
+
+    #[test]
+    fn cannot_debug_secret() {
+        let value = Secret::new("hunter2".to_string());
+        assert_eq!(format!("{:?}", value), "<no debug: alloc::string::String>");
+    }
+
+    #[test]
+    fn can_show_custom_message() {
+        let value: Secret<String, Ellipses> = "hunter2".to_string().into();
+        assert_eq!(format!("{:?}", value), "...");
+        assert_eq!(format!("{}", value), "...");
+    }
+
+    #[test]
+    fn exposes_secret() {
+        let mut value = Secret::new("hunter2".to_string());
+        assert_eq!(value.expose(), "hunter2");
+        value.expose_mut().push('!');
+        assert_eq!(value.expose(), "hunter2!");
+    }
+
+    #[test]
+    fn zeroizes_on_drop() {
+        let zeroized = Rc::new(Cell::new(false));
+        let value = Secret::new(Tracked(zeroized.clone()));
+        assert!(!zeroized.get());
+        drop(value);
+        assert!(zeroized.get());
+    }
+
+    #[test]
+    fn clones_opted_in_secrets() {
+        let zeroized = Rc::new(Cell::new(false));
+        let value = Secret::new(Tracked(zeroized.clone()));
+        drop(value.clone());
+        assert!(zeroized.get());
+    }
+
+    #[test]
+    fn zeroizes_nodebug() {
+        let mut value = NoDebug::new("hunter2".to_string());
+        value.zeroize();
+        assert_eq!(*value, "");
+    }
+
+    #[cfg(feature = "reveal")]
+    #[test]
+    fn reveals_revealable_secrets() {
+        let value: Secret<String, crate::Revealable<Ellipses>> = "hunter2".to_string().into();
+        assert_eq!(
+            crate::reveal_scope(|| format!("{:?}", value)),
+            "\"hunter2\""
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{
+    policy, Ellipses, Fingerprint, Masked, MaxDepth, Msg, NoDebug, Revealable, ShowFirst, ShowLast,
+    Summarize, Summary, Truncate, WithTypeInfo,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// [MsgSerialize] extends [Msg] to choose how [NoDebug] values are serialized.
+///
+/// Markers that only hide values to keep logs readable can pass the value through using
+/// [serialize_transparent], while markers used for secrets should write a placeholder using
+/// [serialize_redacted].
+pub trait MsgSerialize<T: ?Sized>: Msg<T> {
+    /// Serializes the value, or a placeholder for it, to the given serializer.
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+/// Serializes the value as is, for use by display-only [MsgSerialize] implementations.
+pub fn serialize_transparent<T: Serialize + ?Sized, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize(serializer)
+}
+
+/// Serializes the message printed by `M` as a string in place of the value.
+pub fn serialize_redacted<T: ?Sized, M: Msg<T>, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&MsgFmt::<T, M>(value, core::marker::PhantomData))
+}
+
+/// Displays a value using `M`.
+struct MsgFmt<'a, T: ?Sized, M: Msg<T>>(&'a T, core::marker::PhantomData<fn() -> M>);
+
+impl<T: ?Sized, M: Msg<T>> core::fmt::Display for MsgFmt<'_, T, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        M::fmt(self.0, f)
+    }
+}
+
+impl<T: ?Sized> MsgSerialize<T> for WithTypeInfo {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_redacted::<T, Self, S>(value, serializer)
+    }
+}
+
+impl<T: ?Sized> MsgSerialize<T> for Ellipses {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_redacted::<T, Self, S>(value, serializer)
+    }
+}
+
+impl<T: core::hash::Hash + ?Sized> MsgSerialize<T> for Fingerprint {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_redacted::<T, Self, S>(value, serializer)
+    }
+}
+
+/// Serializes values as they are when the current [Policy](policy::Policy) shows their level, and
+/// as the printed message otherwise.
+macro_rules! serialize_levels {
+    ($($level: ident),*) => {
+        $(
+            impl<T: core::fmt::Debug + core::hash::Hash + Serialize + ?Sized> MsgSerialize<T>
+                for policy::$level
+            {
+                fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+                    let sensitivity = <Self as policy::Level>::SENSITIVITY;
+                    match policy::policy().treatment(sensitivity) {
+                        policy::Treatment::Show => serialize_transparent(value, serializer),
+                        _ => serialize_redacted::<T, Self, S>(value, serializer),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+serialize_levels!(Public, Internal, Pii, Secret);
+
+impl<T: AsRef<str> + ?Sized, const N: usize, const C: char> MsgSerialize<T> for ShowLast<N, C> {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_redacted::<T, Self, S>(value, serializer)
+    }
+}
+
+impl<T: AsRef<str> + ?Sized, const N: usize, const C: char> MsgSerialize<T> for ShowFirst<N, C> {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_redacted::<T, Self, S>(value, serializer)
+    }
+}
+
+impl<T: AsRef<str> + ?Sized, const C: char> MsgSerialize<T> for Masked<C> {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_redacted::<T, Self, S>(value, serializer)
+    }
+}
+
+impl<T: Summarize + Serialize + ?Sized> MsgSerialize<T> for Summary {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_transparent(value, serializer)
+    }
+}
+
+impl<T: core::fmt::Debug + Serialize + ?Sized, const N: usize> MsgSerialize<T> for Truncate<N> {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_transparent(value, serializer)
+    }
+}
+
+impl<T: core::fmt::Debug + Serialize + ?Sized, const N: usize> MsgSerialize<T> for MaxDepth<N> {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_transparent(value, serializer)
+    }
+}
+
+impl<T: core::fmt::Debug + ?Sized, M: MsgSerialize<T>> MsgSerialize<T> for Revealable<M> {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        M::serialize(value, serializer)
+    }
+}
+
+impl<T: ?Sized, M: MsgSerialize<T>> Serialize for NoDebug<T, M> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        M::serialize(&self.1, serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, M: Msg<T>> Deserialize<'de> for NoDebug<T, M> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use std::prelude::rust_2021::*;
+
+    struct Verbose;
+
+    impl<T> Msg<T> for Verbose {
+        fn fmt(_value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+            write!(f, "<verbose>")
+        }
+    }
+
+    impl<T: Serialize> MsgSerialize<T> for Verbose {
+        fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize_transparent(value, serializer)
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Config {
+        username: String,
+        password: NoDebug<String>,
+        posts: NoDebug<Vec<String>, Verbose>,
+    }
+
+    #[test]
+    fn deserializes_transparently() {
+        let config: Config = serde_json::from_str(
+            r#"{"username":"Cypher1","password":"hunter2","posts":["post 1"]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.password, "hunter2".to_string());
+        assert_eq!(config.posts, vec!["post 1".to_string()]);
+    }
+
+    #[test]
+    fn serializes_type_info_as_redacted() {
+        let value: NoDebug<String> = "hunter2".to_string().into();
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#""<no debug: alloc::string::String>""#
+        );
+    }
+
+    #[test]
+    fn serializes_ellipses_as_redacted() {
+        let value: NoDebug<i32, Ellipses> = 3.into();
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#""...""#);
+    }
+
+    #[test]
+    fn serializes_partial_reveals_as_redacted() {
+        let value: NoDebug<&str, ShowLast<4>> = "sk_live_abcd".into();
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#""********abcd""#);
+    }
+
+    #[test]
+    fn serializes_summaries_transparently() {
+        let value: NoDebug<Vec<i32>, Summary> = vec![1, 2, 3].into();
+        assert_eq!(serde_json::to_string(&value).unwrap(), "[1,2,3]");
+    }
+
+    #[test]
+    fn serializes_display_only_markers_transparently() {
+        let config = Config {
+            username: "Cypher1".to_string(),
+            password: "hunter2".to_string().into(),
+            posts: vec!["post 1".to_string()].into(),
+        };
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"username":"Cypher1","password":"<no debug: alloc::string::String>","posts":["post 1"]}"#
+        );
+    }
+
+    #[test]
+    fn serializes_levels_using_policy() {
+        use policy::{Pii, Policy, Treatment};
+
+        let email: NoDebug<&str, policy::Public> = "alice@example.com".into();
+        let id: NoDebug<u32, Pii> = 42.into();
+        let secret: NoDebug<u32, policy::Secret> = 42.into();
+
+        policy::with_policy(Policy::default(), || {
+            assert_eq!(
+                serde_json::to_string(&email).unwrap(),
+                r#""alice@example.com""#
+            );
+            assert!(serde_json::to_string(&id)
+                .unwrap()
+                .starts_with(r#""<redacted #"#));
+        });
+
+        let policy = Policy {
+            public: Treatment::Hide,
+            pii: Treatment::Show,
+            secret: Treatment::Show,
+            ..Policy::default()
+        };
+        policy::with_policy(policy, || {
+            assert_eq!(serde_json::to_string(&email).unwrap(), r#""...""#);
+            assert_eq!(serde_json::to_string(&id).unwrap(), "42");
+            assert_eq!(serde_json::to_string(&secret).unwrap(), r#""...""#);
+        });
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{Msg, MsgDisplay};
+#[cfg(feature = "alloc")]
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    string::String,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+/// [Summarize] is a trait for describing the shape of a value without its contents, for use by
+/// [Summary].
+pub trait Summarize {
+    /// Prints a short description of the value, e.g. `12 entries`.
+    fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error>;
+}
+
+/// Prints the (shortened) type of the value and a summary from its [Summarize] impl, e.g.
+/// `<Vec<String>: 1532 items>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Summary;
+
+impl<T: Summarize + ?Sized> Msg<T> for Summary {
+    fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        write!(f, "<")?;
+        write_short_type_name(core::any::type_name::<T>(), f)?;
+        write!(f, ": ")?;
+        value.summarize(f)?;
+        write!(f, ">")
+    }
+}
+
+impl<T: Summarize + ?Sized> MsgDisplay<T> for Summary {}
+
+/// Prints a type name without module paths, e.g. `Vec<String>` instead of
+/// `alloc::vec::Vec<alloc::string::String>`.
+fn write_short_type_name(name: &str, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+    let mut write_path = |path: &str| write!(f, "{}", path.rsplit("::").next().unwrap_or(path));
+    let mut start = 0;
+    for (index, c) in name.char_indices() {
+        if "<>,;()[]&* ".contains(c) {
+            write_path(&name[start..index])?;
+            write_path(&name[index..index + c.len_utf8()])?;
+            start = index + c.len_utf8();
+        }
+    }
+    write_path(&name[start..])
+}
+
+fn count(
+    f: &mut core::fmt::Formatter,
+    count: usize,
+    noun: &str,
+    plural: &str,
+) -> Result<(), core::fmt::Error> {
+    write!(f, "{} {}", count, if count == 1 { noun } else { plural })
+}
+
+macro_rules! summarize_items {
+    ($($(#[$attr: meta])* <$($param: ident),*> $ty: ty),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            impl<$($param),*> Summarize for $ty {
+                fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+                    count(f, self.len(), "item", "items")
+                }
+            }
+        )*
+    };
+}
+
+summarize_items!(
+    <T> [T],
+    #[cfg(feature = "alloc")]
+    <T> Vec<T>,
+    #[cfg(feature = "alloc")]
+    <T> VecDeque<T>,
+    #[cfg(feature = "alloc")]
+    <T> LinkedList<T>,
+    #[cfg(feature = "alloc")]
+    <T> BTreeSet<T>,
+    #[cfg(feature = "alloc")]
+    <T> BinaryHeap<T>,
+    #[cfg(feature = "std")]
+    <T, S> HashSet<T, S>,
+);
+
+impl<T, const N: usize> Summarize for [T; N] {
+    fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        count(f, N, "item", "items")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> Summarize for BTreeMap<K, V> {
+    fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        count(f, self.len(), "entry", "entries")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Summarize for HashMap<K, V, S> {
+    fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        count(f, self.len(), "entry", "entries")
+    }
+}
+
+impl Summarize for str {
+    fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        count(f, self.len(), "byte", "bytes")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Summarize for String {
+    fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        self.as_str().summarize(f)
+    }
+}
+
+impl<T: Summarize> Summarize for Option<T> {
+    fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        match self {
+            Some(value) => {
+                write!(f, "Some(")?;
+                value.summarize(f)?;
+                write!(f, ")")
+            }
+            None => write!(f, "None"),
+        }
+    }
+}
+
+impl<T: Summarize + ?Sized> Summarize for &T {
+    fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        (**self).summarize(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Summarize + ?Sized> Summarize for Box<T> {
+    fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        (**self).summarize(f)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::NoDebug;
+    use std::prelude::rust_2021::*;
+
+    #[test]
+    fn summarizes_vec() {
+        let value: NoDebug<Vec<String>, Summary> =
+            vec!["post 1".to_string(), "post 2".to_string()].into();
+        assert_eq!(format!("{:?}", value), "<Vec<String>: 2 items>");
+        assert_eq!(format!("{}", value), "<Vec<String>: 2 items>");
+    }
+
+    #[test]
+    fn summarizes_single_item() {
+        let value: NoDebug<[i32; 1], Summary> = [3].into();
+        assert_eq!(format!("{:?}", value), "<[i32; 1]: 1 item>");
+    }
+
+    #[test]
+    fn summarizes_maps() {
+        let value: NoDebug<HashMap<String, Vec<i32>>, Summary> =
+            HashMap::from([("a".to_string(), vec![]), ("b".to_string(), vec![])]).into();
+        assert_eq!(
+            format!("{:?}", value),
+            "<HashMap<String, Vec<i32>>: 2 entries>"
+        );
+        let value: NoDebug<BTreeMap<i32, i32>, Summary> = BTreeMap::new().into();
+        assert_eq!(format!("{:?}", value), "<BTreeMap<i32, i32>: 0 entries>");
+    }
+
+    #[test]
+    fn summarizes_strings() {
+        let value: NoDebug<String, Summary> = "hunter2".to_string().into();
+        assert_eq!(format!("{:?}", value), "<String: 7 bytes>");
+        let value: NoDebug<&str, Summary> = "hunter2".into();
+        assert_eq!(format!("{:?}", value), "<&str: 7 bytes>");
+    }
+
+    #[test]
+    fn summarizes_options() {
+        let value: NoDebug<Option<Vec<i32>>, Summary> = Some(vec![1, 2, 3]).into();
+        assert_eq!(format!("{:?}", value), "<Option<Vec<i32>>: Some(3 items)>");
+        let value: NoDebug<Option<Vec<i32>>, Summary> = None.into();
+        assert_eq!(format!("{:?}", value), "<Option<Vec<i32>>: None>");
+    }
+
+    struct Tree {
+        depth: usize,
+    }
+
+    impl Summarize for Tree {
+        fn summarize(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+            write!(f, "depth {}", self.depth)
+        }
+    }
+
+    #[test]
+    fn summarizes_custom_types() {
+        let value: NoDebug<Tree, Summary> = Tree { depth: 3 }.into();
+        assert_eq!(format!("{:?}", value), "<Tree: depth 3>");
+    }
+}
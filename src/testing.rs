@@ -0,0 +1,277 @@
+//! Helpers for checking that values are not leaked by `Debug` output or panic messages.
+//!
+//! # Guarantees
+//!
+//! - `Debug` and `Display` for [NoDebug] (and [Sealed](crate::Sealed)) only print the output of the
+//!   [Msg] type, so any panic message that formats a value with `{:?}`, e.g. from
+//!   [Result::unwrap] or [assert_eq], contains the [Msg] output rather than the value.
+//! - [Msg] types can print the value's own [Debug] output when that is their purpose (e.g.
+//!   [Truncate](crate::Truncate)), or in reveal mode if they are [Msg::REVEALABLE].
+//! - [assert_eq_redacted](crate::assert_eq_redacted) and
+//!   [assert_ne_redacted](crate::assert_ne_redacted) never reveal values: [NoDebug] operands are
+//!   printed with [Msg::fmt] even in reveal mode, and any other operands are printed like
+//!   [WithTypeInfo]. Only the comparison and an optional message are reported, not the operands'
+//!   source code, which could contain secrets.
+
+use crate::{Msg, NoDebug, WithTypeInfo};
+use core::fmt::Debug;
+
+/// Panics if the [Debug] output of `value`, plain or pretty printed, contains `secret`.
+///
+/// The panic message does not include the output or the secret.
+#[cfg(feature = "alloc")]
+#[track_caller]
+pub fn assert_not_leaked<T: Debug + ?Sized>(value: &T, secret: &str) {
+    assert!(
+        !alloc::format!("{:?}", value).contains(secret),
+        "the Debug output of the value contains the secret"
+    );
+    assert!(
+        !alloc::format!("{:#?}", value).contains(secret),
+        "the pretty Debug output of the value contains the secret"
+    );
+}
+
+/// Formats a value using `M`, ignoring reveal mode.
+#[doc(hidden)]
+pub struct Redacted<'a, T, M: Msg<T>>(&'a T, core::marker::PhantomData<fn() -> M>);
+
+impl<T, M: Msg<T>> Debug for Redacted<'_, T, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        M::fmt(self.0, f)
+    }
+}
+
+/// Borrows an operand of [assert_eq_redacted](crate::assert_eq_redacted), choosing how to print it
+/// via [RedactNoDebug] or [RedactAny].
+#[doc(hidden)]
+pub struct Redact<'a, T>(pub &'a T);
+
+/// Prints [NoDebug] values using their [Msg] type.
+#[doc(hidden)]
+pub trait RedactNoDebug {
+    type Value;
+    type Msg: Msg<Self::Value>;
+    fn redacted(&self) -> Redacted<'_, Self::Value, Self::Msg>;
+}
+
+impl<T, M: Msg<T>> RedactNoDebug for Redact<'_, NoDebug<T, M>> {
+    type Value = T;
+    type Msg = M;
+    fn redacted(&self) -> Redacted<'_, T, M> {
+        Redacted(&self.0 .1, core::marker::PhantomData)
+    }
+}
+
+/// Prints any other values like [WithTypeInfo].
+#[doc(hidden)]
+pub trait RedactAny {
+    type Value;
+    fn redacted(&self) -> Redacted<'_, Self::Value, WithTypeInfo>;
+}
+
+impl<T> RedactAny for &Redact<'_, T> {
+    type Value = T;
+    fn redacted(&self) -> Redacted<'_, T, WithTypeInfo> {
+        Redacted(self.0, core::marker::PhantomData)
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_failed(
+    op: &str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    args: Option<core::fmt::Arguments>,
+) -> ! {
+    match args {
+        Some(args) => panic!(
+            "assertion `left {} right` failed: {}\n  left: {:?}\n right: {:?}",
+            op, args, left, right
+        ),
+        None => panic!(
+            "assertion `left {} right` failed\n  left: {:?}\n right: {:?}",
+            op, left, right
+        ),
+    }
+}
+
+/// Asserts that two expressions are equal, like [assert_eq], without revealing either value in
+/// the failure message.
+///
+/// [NoDebug] values are printed using their [Msg] type (ignoring reveal mode), and other values are
+/// printed like [WithTypeInfo]. See the [testing](crate::testing) module for details.
+/// ```rust,should_panic
+/// use no_debug::{assert_eq_redacted, NoDebug};
+///
+/// let password: NoDebug<&str> = "hunter2".into();
+/// // Panics with "assertion `left == right` failed\n  left: <no debug: &str>\n right: <no debug: &str>"
+/// assert_eq_redacted!(password, "hunter3");
+/// ```
+#[macro_export]
+macro_rules! assert_eq_redacted {
+    ($left: expr, $right: expr $(,)?) => {
+        $crate::__assert_redacted!(==, $left, $right, ::core::option::Option::None)
+    };
+    ($left: expr, $right: expr, $($arg: tt)+) => {
+        $crate::__assert_redacted!(
+            ==,
+            $left,
+            $right,
+            ::core::option::Option::Some(::core::format_args!($($arg)+))
+        )
+    };
+}
+
+/// Asserts that two expressions are not equal, like [assert_ne], without revealing either value in
+/// the failure message.
+///
+/// See [assert_eq_redacted](crate::assert_eq_redacted).
+#[macro_export]
+macro_rules! assert_ne_redacted {
+    ($left: expr, $right: expr $(,)?) => {
+        $crate::__assert_redacted!(!=, $left, $right, ::core::option::Option::None)
+    };
+    ($left: expr, $right: expr, $($arg: tt)+) => {
+        $crate::__assert_redacted!(
+            !=,
+            $left,
+            $right,
+            ::core::option::Option::Some(::core::format_args!($($arg)+))
+        )
+    };
+}
+
+/// Like [assert_eq_redacted](crate::assert_eq_redacted), but only checked in debug builds.
+#[macro_export]
+macro_rules! debug_assert_eq_redacted {
+    ($($arg: tt)*) => {
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_eq_redacted!($($arg)*);
+        }
+    };
+}
+
+/// Like [assert_ne_redacted](crate::assert_ne_redacted), but only checked in debug builds.
+#[macro_export]
+macro_rules! debug_assert_ne_redacted {
+    ($($arg: tt)*) => {
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_ne_redacted!($($arg)*);
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_redacted {
+    ($op: tt, $left: expr, $right: expr, $args: expr) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left $op *right) {
+                    #[allow(unused_imports)]
+                    use $crate::testing::{RedactAny as _, RedactNoDebug as _};
+                    $crate::testing::assert_failed(
+                        ::core::stringify!($op),
+                        &(&$crate::testing::Redact(left)).redacted(),
+                        &(&$crate::testing::Redact(right)).redacted(),
+                        $args,
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ellipses;
+    use std::prelude::rust_2021::*;
+
+    fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+        let payload = std::panic::catch_unwind(f).expect_err("expected a panic");
+        match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload.downcast::<&str>().unwrap().to_string(),
+        }
+    }
+
+    #[test]
+    fn unwrap_does_not_leak() {
+        let message = panic_message(|| {
+            let result: Result<(), NoDebug<&str>> = core::hint::black_box(Err("hunter2".into()));
+            result.unwrap();
+        });
+        assert_eq!(
+            message,
+            "called `Result::unwrap()` on an `Err` value: <no debug: &str>"
+        );
+    }
+
+    #[test]
+    fn assert_eq_does_not_leak() {
+        let message = panic_message(|| {
+            let value: NoDebug<&str, Ellipses> = "hunter2".into();
+            let other: NoDebug<&str, Ellipses> = "hunter3".into();
+            assert_eq!(value, other);
+        });
+        assert!(!message.contains("hunter"), "{}", message);
+    }
+
+    #[test]
+    fn passes_redacted_assertions() {
+        let value: NoDebug<&str> = "hunter2".into();
+        assert_eq_redacted!(value, "hunter2");
+        assert_ne_redacted!(value, "hunter3", "values should differ");
+        debug_assert_eq_redacted!(value, NoDebug::new("hunter2"));
+        debug_assert_ne_redacted!(value, NoDebug::new("hunter3"));
+    }
+
+    #[test]
+    fn assert_eq_redacted_uses_msg() {
+        let message = panic_message(|| {
+            let value: NoDebug<&str, Ellipses> = "hunter2".into();
+            let other: NoDebug<&str> = "hunter3".into();
+            assert_eq_redacted!(value, other);
+        });
+        assert_eq!(
+            message,
+            "assertion `left == right` failed\n  left: ...\n right: <no debug: &str>"
+        );
+    }
+
+    #[test]
+    fn assert_eq_redacted_hides_raw_values() {
+        let message = panic_message(|| {
+            let value: NoDebug<&str, Ellipses> = "hunter2".into();
+            assert_eq_redacted!(value, "hunter3", "checking {}", "password");
+        });
+        assert_eq!(
+            message,
+            "assertion `left == right` failed: checking password\n  left: ...\n right: <no debug: &str>"
+        );
+    }
+
+    #[test]
+    fn assert_ne_redacted_reports_comparison() {
+        let message = panic_message(|| {
+            let value: NoDebug<i32> = 3.into();
+            assert_ne_redacted!(value, 3);
+        });
+        assert_eq!(
+            message,
+            "assertion `left != right` failed\n  left: <no debug: i32>\n right: <no debug: i32>"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn checks_for_leaks() {
+        let value: NoDebug<&str> = "hunter2".into();
+        assert_not_leaked(&value, "hunter2");
+        let message = panic_message(|| assert_not_leaked(&"hunter2", "hunter2"));
+        assert!(!message.contains("hunter2"));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{Msg, NoDebug};
+use tracing::field::DebugValue;
+
+/// Wraps a [NoDebug] value for recording as a `tracing` field.
+///
+/// The field is always recorded via `record_debug` with the [Msg] output, so wrapped numbers,
+/// strings or errors can't be recorded via `record_i64`, `record_str` etc. (which would bypass
+/// their [Msg] type).
+/// ```rust
+/// use no_debug::NoDebug;
+///
+/// let password: NoDebug<String> = "hunter2".to_string().into();
+/// tracing::info!(password = no_debug::field(&password), "logging in");
+/// ```
+pub fn field<T: ?Sized, M: Msg<T>>(value: &NoDebug<T, M>) -> DebugValue<&NoDebug<T, M>> {
+    tracing::field::debug(value)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{Ellipses, WithTypeInfo};
+    use std::prelude::rust_2021::*;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Records every field as `name kind: value`.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    impl Capture {
+        fn push(&self, field: &Field, kind: &str, value: &dyn core::fmt::Debug) {
+            let line = format!("{} {}: {:?}", field.name(), kind, value);
+            self.0.lock().unwrap().push(line);
+        }
+
+        fn lines(&self) -> Vec<String> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl Visit for Capture {
+        fn record_i64(&mut self, field: &Field, value: i64) {
+            self.push(field, "i64", &value);
+        }
+
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            self.push(field, "u64", &value);
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.push(field, "str", &value);
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn core::fmt::Debug) {
+            self.push(field, "debug", value);
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes) -> Id {
+            span.record(&mut self.clone());
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, values: &Record) {
+            values.record(&mut self.clone());
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event) {
+            event.record(&mut self.clone());
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[test]
+    fn records_redacted_strings() {
+        let capture = Capture::default();
+        let password: NoDebug<&str> = "hunter2".into();
+        tracing::subscriber::with_default(capture.clone(), || {
+            tracing::info!(password = field(&password), "logging in");
+        });
+        let lines = capture.lines();
+        assert!(lines.contains(&"password debug: <no debug: &str>".to_string()));
+        assert!(lines.iter().all(|line| !line.contains("hunter2")));
+    }
+
+    #[test]
+    fn records_redacted_numbers() {
+        let capture = Capture::default();
+        let pin: NoDebug<i64, Ellipses> = 1234.into();
+        tracing::subscriber::with_default(capture.clone(), || {
+            tracing::info!(pin = field(&pin));
+        });
+        let lines = capture.lines();
+        assert!(lines.contains(&"pin debug: ...".to_string()));
+        assert!(lines.iter().all(|line| !line.contains("1234")));
+    }
+
+    #[test]
+    fn records_redacted_span_fields() {
+        let capture = Capture::default();
+        let token: NoDebug<u64, WithTypeInfo> = 42.into();
+        tracing::subscriber::with_default(capture.clone(), || {
+            let span = tracing::info_span!("request", token = tracing::field::Empty);
+            span.record("token", field(&token));
+        });
+        assert_eq!(capture.lines(), vec!["token debug: <no debug: u64>"]);
+    }
+
+    #[test]
+    fn records_unsized_values() {
+        let capture = Capture::default();
+        let password: &NoDebug<str, Ellipses> = NoDebug::from_ref("hunter2");
+        tracing::subscriber::with_default(capture.clone(), || {
+            tracing::info!(password = field(password));
+        });
+        assert_eq!(capture.lines(), vec!["password debug: ..."]);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{Msg, MsgDisplay};
+use core::fmt::{Debug, Write};
+
+/// Prints the [Debug] output of the value, cut off after `N` characters, e.g.
+/// `["long post 1...", "lo… (+40 more)`.
+///
+/// Pretty printing with `{:#?}` is preserved, with the limit applying to the pretty output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Truncate<const N: usize>;
+
+impl<T: Debug + ?Sized, const N: usize> Msg<T> for Truncate<N> {
+    const REVEALABLE: bool = true;
+
+    fn fmt(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        let alternate = f.alternate();
+        let mut writer = TruncatingWriter {
+            f,
+            remaining: N,
+            truncated: 0,
+        };
+        if alternate {
+            write!(writer, "{:#?}", value)?;
+        } else {
+            write!(writer, "{:?}", value)?;
+        }
+        let truncated = writer.truncated;
+        if truncated > 0 {
+            write!(f, "… (+{} more)", truncated)?;
+        }
+        Ok(())
+    }
+
+    fn reveal(value: &T, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        value.fmt(f)
+    }
+}
+
+impl<T: Debug + ?Sized, const N: usize> MsgDisplay<T> for Truncate<N> {}
+
+/// Writes up to `remaining` characters to the formatter, counting the characters that are cut off.
+struct TruncatingWriter<'a, 'b> {
+    f: &'a mut core::fmt::Formatter<'b>,
+    remaining: usize,
+    truncated: usize,
+}
+
+impl Write for TruncatingWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> Result<(), core::fmt::Error> {
+        let split = s
+            .char_indices()
+            .nth(self.remaining)
+            .map_or(s.len(), |(index, _)| index);
+        let (shown, cut) = s.split_at(split);
+        self.f.write_str(shown)?;
+        self.remaining -= shown.chars().count();
+        self.truncated += cut.chars().count();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoDebug;
+    use std::prelude::rust_2021::*;
+
+    #[test]
+    fn truncates_long_values() {
+        let value: NoDebug<Vec<i32>, Truncate<10>> = vec![1, 2, 3, 4, 5, 6, 7].into();
+        assert_eq!(format!("{:?}", value), "[1, 2, 3, … (+11 more)");
+        assert_eq!(format!("{}", value), "[1, 2, 3, … (+11 more)");
+    }
+
+    #[test]
+    fn does_not_truncate_short_values() {
+        let value: NoDebug<Vec<i32>, Truncate<10>> = vec![1, 2].into();
+        assert_eq!(format!("{:?}", value), "[1, 2]");
+        let value: NoDebug<&str, Truncate<4>> = "ab".into();
+        assert_eq!(format!("{:?}", value), r#""ab""#);
+    }
+
+    #[test]
+    fn truncates_by_characters() {
+        let value: NoDebug<&str, Truncate<3>> = "héllo".into();
+        assert_eq!(format!("{:?}", value), r#""hé… (+4 more)"#);
+    }
+
+    #[test]
+    fn preserves_pretty_printing() {
+        let value: NoDebug<Vec<i32>, Truncate<14>> = vec![1, 2, 3].into();
+        assert_eq!(format!("{:#?}", value), "[\n    1,\n    2… (+10 more)");
+    }
+}